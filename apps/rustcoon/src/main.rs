@@ -1,8 +1,14 @@
+mod maintenance;
 mod monolith;
 
 use rustcoon_orchestration::OrchestratorError;
 
 #[tokio::main]
 async fn main() -> Result<(), OrchestratorError> {
-    monolith::run().await
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.is_empty() {
+        monolith::run().await
+    } else {
+        maintenance::run(&args).await
+    }
 }
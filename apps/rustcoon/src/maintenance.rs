@@ -0,0 +1,57 @@
+use rustcoon_orchestration::{
+    OrchestratorError, build_catalog_ports, list_instance_versions, restore_instance_version,
+    storage_layout_report,
+};
+use rustcoon_storage_filesystem::ENTRY_COUNT_BUCKETS;
+
+const USAGE: &str = "usage: rustcoon versions list <sop-instance-uid>\n       \
+                     rustcoon versions restore <sop-instance-uid> <blob-key>\n       \
+                     rustcoon storage report";
+
+/// Runs an operator command against the configured catalog and exits.
+pub async fn run(args: &[String]) -> Result<(), OrchestratorError> {
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let config = rustcoon_config::MonolithConfig::load()?;
+    match args.as_slice() {
+        ["versions", "list", sop_instance_uid] => {
+            let catalog_ports = build_catalog_ports(&config).await?;
+            for version in list_instance_versions(&catalog_ports, sop_instance_uid).await? {
+                println!(
+                    "{}\t{}\t{}",
+                    version.key,
+                    version
+                        .size_bytes
+                        .map_or_else(|| "-".to_string(), |size| size.to_string()),
+                    version.version.as_deref().unwrap_or("-"),
+                );
+            }
+            Ok(())
+        }
+        ["versions", "restore", sop_instance_uid, blob_key] => {
+            let catalog_ports = build_catalog_ports(&config).await?;
+            restore_instance_version(&catalog_ports, sop_instance_uid, blob_key).await?;
+            println!("restored {blob_key} for {sop_instance_uid}");
+            Ok(())
+        }
+        ["storage", "report"] => {
+            let report = storage_layout_report(&config).await?;
+            println!("directories\t{}", report.directories);
+            println!("files\t{}", report.files);
+            let mut lower = 0;
+            for (index, count) in report.directories_by_entry_count.iter().enumerate() {
+                match ENTRY_COUNT_BUCKETS.get(index) {
+                    Some(&upper) => {
+                        println!("directories with {lower}-{} entries\t{count}", upper - 1);
+                        lower = upper;
+                    }
+                    None => println!("directories with {lower}+ entries\t{count}"),
+                }
+            }
+            for (path, entries) in &report.largest_directories {
+                println!("{entries}\t{}", path.display());
+            }
+            Ok(())
+        }
+        _ => Err(OrchestratorError::Maintenance(USAGE.to_string())),
+    }
+}
@@ -7,7 +7,7 @@
     DimseServiceSelection, OrchestratorError, build_blob_store, build_catalog_ports,
     build_dimse_service_registries, build_ingest_service, build_query_service,
     build_retrieve_service, init_telemetry, install_ctrl_c_handler, run_runtime,
-    start_listener_for_ae,
+    start_listener_for_ae, start_storage_layout_metrics,
 };
 use rustcoon_runtime::{FatalRuntimeError, Runtime, RuntimeApp};
 use tokio::sync::{Semaphore, mpsc};
@@ -21,20 +21,21 @@ pub async fn run() -> Result<(), OrchestratorError> {
     let ae_registry = build_ae_registry(&config)?;
     let blob_store = build_blob_store(&config);
     let catalog_ports = build_catalog_ports(&config).await?;
-    let ingest = build_ingest_service(blob_store.clone(), &catalog_ports);
-    let query = build_query_service(&catalog_ports);
+    let ingest = build_ingest_service(blob_store.clone(), &catalog_ports, &config.ingest)?;
+    let query = build_query_service(&catalog_ports, &config.query)?;
     let retrieve = build_retrieve_service(blob_store.clone(), &catalog_ports);
     let service_registries = build_dimse_service_registries(
         Arc::clone(&ae_registry),
         Some(ingest),
         Some(query),
         Some(retrieve),
-        DimseServiceSelection::monolith_default(),
+        DimseServiceSelection::from_config(&config.runtime.dimse.services),
     )?;
     let app = MonolithApp::new(
         ae_registry,
         service_registries,
         config.runtime.dimse.clone(),
+        config.filesystem.clone(),
     );
     let runtime = Runtime::new(app, config.runtime);
 
@@ -47,6 +48,7 @@ struct MonolithApp {
     ae_registry: Arc<ApplicationEntityRegistry>,
     service_registries: std::collections::HashMap<String, Arc<ServiceClassRegistry>>,
     runtime_dimse: rustcoon_config::runtime::RuntimeDimseConfig,
+    filesystem: rustcoon_config::storage::FilesystemConfig,
 }
 
 impl MonolithApp {
@@ -54,11 +56,13 @@ fn new(
         ae_registry: Arc<ApplicationEntityRegistry>,
         service_registries: std::collections::HashMap<String, Arc<ServiceClassRegistry>>,
         runtime_dimse: rustcoon_config::runtime::RuntimeDimseConfig,
+        filesystem: rustcoon_config::storage::FilesystemConfig,
     ) -> Self {
         Self {
             ae_registry,
             service_registries,
             runtime_dimse,
+            filesystem,
         }
     }
 
@@ -110,6 +114,7 @@ fn start(
         task_tracker: &TaskTracker,
         fatal_tx: mpsc::UnboundedSender<FatalRuntimeError>,
     ) {
+        start_storage_layout_metrics(&self.filesystem, shutdown.clone(), task_tracker);
         if let Err(error) = self.start_dimse_listeners(shutdown, task_tracker, fatal_tx.clone()) {
             let _ = fatal_tx.send(FatalRuntimeError::new(
                 "dimse.listener",
@@ -2,6 +2,7 @@
 pub struct PostgresCatalogConfig {
     connection_string: String,
     max_connections: u32,
+    schema: Option<String>,
 }
 
 impl PostgresCatalogConfig {
@@ -9,6 +10,7 @@ pub fn new(connection_string: impl Into<String>) -> Self {
         Self {
             connection_string: connection_string.into(),
             max_connections: 10,
+            schema: None,
         }
     }
 
@@ -17,6 +19,12 @@ pub fn with_max_connections(mut self, max_connections: u32) -> Self {
         self
     }
 
+    /// Places catalog tables in `schema` by setting the session `search_path`.
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
     pub fn connection_string(&self) -> &str {
         &self.connection_string
     }
@@ -24,6 +32,10 @@ pub fn connection_string(&self) -> &str {
     pub fn max_connections(&self) -> u32 {
         self.max_connections
     }
+
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -37,5 +49,13 @@ fn config_builder_clamps_pool_size() {
 
         assert_eq!(config.connection_string(), "postgres://localhost/rustcoon");
         assert_eq!(config.max_connections(), 1);
+        assert_eq!(config.schema(), None);
+    }
+
+    #[test]
+    fn config_builder_sets_schema() {
+        let config = PostgresCatalogConfig::new("postgres://localhost/shared").with_schema("pacs");
+
+        assert_eq!(config.schema(), Some("pacs"));
     }
 }
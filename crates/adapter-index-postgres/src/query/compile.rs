@@ -1,14 +1,17 @@
 use dicom_core::VR;
 use dicom_core::dictionary::{DataDictionary, DataDictionaryEntry};
-use dicom_dictionary_std::StandardDataDictionary;
+use dicom_dictionary_std::{StandardDataDictionary, tags};
 use dicom_object::InMemDicomObject;
+use rustcoon_dicom::fold_person_name;
 use rustcoon_index::{
     AttributePath, AttributePathSegment, CatalogQuery, CatalogQueryEntry, IndexError, ItemSelector,
     MatchingRule, Paging, PatientRootQueryRetrieveLevel, Predicate, QueryRetrieveScope,
     SequenceMatching, SortDirection, SortKey, StudyRootQueryRetrieveLevel,
 };
 
-use crate::schema::{CatalogSchema, TableId, format_tag_key};
+use crate::schema::{
+    AttributeMapping, CatalogSchema, MappedVr, TableId, format_tag_key, top_level_tag,
+};
 use crate::schema::{INSTANCES, SERIES, STUDIES};
 
 #[derive(Debug, Clone)]
@@ -85,6 +88,11 @@ pub(crate) fn compile_query(
     let distinct_on = distinct_on_sql(level);
     let mut order_sql = distinct_order_sql(level);
     order_sql.extend(user_sort_sql);
+    // Image rows have no DISTINCT ON keys leading the order, so close it with
+    // the instance UID to keep paging stable across requests.
+    if level == ResultLevel::Image {
+        order_sql.push("i.sop_instance_uid".to_string());
+    }
 
     let select_sql = projections
         .iter()
@@ -186,6 +194,15 @@ fn compile_projection(
 ) -> Result<CompiledProjection, IndexError> {
     let alias = format!("p_{index}");
 
+    if let Some(aggregate) = StudyAggregate::for_path(path) {
+        return Ok(CompiledProjection::Mapped {
+            path: path.clone(),
+            select_sql: aggregate.select_sql(),
+            alias,
+            vr: aggregate.vr(),
+        });
+    }
+
     if let Some(mapping) = schema.attribute_for(path) {
         return Ok(CompiledProjection::Mapped {
             path: path.clone(),
@@ -214,11 +231,13 @@ fn compile_sort(schema: &CatalogSchema, sort: &[SortKey]) -> Result<Vec<String>,
             SortDirection::Descending => "DESC",
         };
 
+        if let Some(aggregate) = StudyAggregate::for_path(path) {
+            order_sql.push(format!("{} {direction}", aggregate.select_sql()));
+            continue;
+        }
+
         if let Some(mapping) = schema.attribute_for(path) {
-            order_sql.push(format!(
-                "{} {direction}",
-                mapped_column_sql(mapping.table, mapping.column)
-            ));
+            order_sql.push(format!("{} {direction}", mapped_order_sql(mapping)));
             continue;
         }
 
@@ -247,6 +266,12 @@ fn compile_predicate(
             "NOT ({})",
             compile_predicate(schema, inner, binds, next_bind)?
         )),
+        Predicate::StudyDateTimeUtc(range) => Ok(compile_text_range(
+            &mapped_column_sql(TableId::Study, "study_date_time_utc"),
+            range,
+            binds,
+            next_bind,
+        )),
         Predicate::Attribute(path, MatchingRule::Sequence(sequence)) => compile_sequence_matching(
             schema,
             DatasetContext::root(),
@@ -255,17 +280,40 @@ fn compile_predicate(
             binds,
             next_bind,
         ),
+        Predicate::Attribute(path, MatchingRule::Universal)
+            if StudyAggregate::for_path(path).is_some() =>
+        {
+            Ok("TRUE".to_string())
+        }
+        Predicate::Attribute(path, rule) if StudyAggregate::for_path(path).is_some() => {
+            let aggregate = StudyAggregate::for_path(path).expect("matched aggregate");
+            let (table, column) = aggregate.source();
+            Ok(format!(
+                "EXISTS (SELECT 1 FROM {table} agg WHERE agg.study_instance_uid = {}.study_instance_uid AND {})",
+                STUDIES.alias,
+                compile_matching_rule(
+                    &format!("agg.{column}"),
+                    Some(aggregate.match_vr()),
+                    rule,
+                    binds,
+                    next_bind
+                )?
+            ))
+        }
         Predicate::Attribute(path, rule) => {
-            let value_sql = if let Some(mapping) = schema.attribute_for(path) {
-                format!("{}::text", mapped_column_sql(mapping.table, mapping.column))
+            let (value_sql, rule) = if let Some(mapping) = schema.attribute_for(path) {
+                mapped_match(mapping, rule)
             } else {
-                json_extract_path_text_sql(
-                    instance_attributes_column(),
-                    &json_value_tokens(path, true, false)?,
+                (
+                    json_extract_path_text_sql(
+                        instance_attributes_column(),
+                        &json_value_tokens(path, true, false)?,
+                    ),
+                    rule.clone(),
                 )
             };
 
-            compile_matching_rule(&value_sql, path_vr(path), rule, binds, next_bind)
+            compile_matching_rule(&value_sql, path_vr(path), &rule, binds, next_bind)
         }
     }
 }
@@ -301,10 +349,9 @@ fn compile_matching_rule(
             Ok(bind_text_predicate(value_sql, "=", value, binds, next_bind))
         }
         MatchingRule::Wildcard(value) => {
-            let like = value.replace('*', "%").replace('?', "_");
-            Ok(bind_text_predicate(
-                value_sql, "LIKE", &like, binds, next_bind,
-            ))
+            let like =
+                bind_text_predicate(value_sql, "LIKE", &like_pattern(value), binds, next_bind);
+            Ok(format!("{like} ESCAPE '\\'"))
         }
         MatchingRule::Universal => Ok("TRUE".to_string()),
         MatchingRule::EmptyValue => Ok(format!("({value_sql} IS NULL OR {value_sql} = '')")),
@@ -327,20 +374,7 @@ fn compile_matching_rule(
             if vr == Some(VR::DT) {
                 return compile_datetime_range(value_sql, range, binds, next_bind);
             }
-            let mut parts = Vec::new();
-            if let Some(start) = &range.start {
-                parts.push(bind_text_predicate(
-                    value_sql, ">=", start, binds, next_bind,
-                ));
-            }
-            if let Some(end) = &range.end {
-                parts.push(bind_text_predicate(value_sql, "<=", end, binds, next_bind));
-            }
-            if parts.is_empty() {
-                Ok("TRUE".to_string())
-            } else {
-                Ok(format!("({})", parts.join(" AND ")))
-            }
+            Ok(compile_text_range(value_sql, range, binds, next_bind))
         }
         MatchingRule::Sequence(_) => Err(IndexError::invalid_attribute_filter(
             "nested sequence matching must be compiled at the predicate layer",
@@ -348,6 +382,28 @@ fn compile_matching_rule(
     }
 }
 
+fn compile_text_range(
+    value_sql: &str,
+    range: &rustcoon_index::RangeMatching,
+    binds: &mut Vec<BindValue>,
+    next_bind: &mut usize,
+) -> String {
+    let mut parts = Vec::new();
+    if let Some(start) = &range.start {
+        parts.push(bind_text_predicate(
+            value_sql, ">=", start, binds, next_bind,
+        ));
+    }
+    if let Some(end) = &range.end {
+        parts.push(bind_text_predicate(value_sql, "<=", end, binds, next_bind));
+    }
+    if parts.is_empty() {
+        "TRUE".to_string()
+    } else {
+        format!("({})", parts.join(" AND "))
+    }
+}
+
 fn compile_datetime_range(
     value_sql: &str,
     range: &rustcoon_index::RangeMatching,
@@ -472,31 +528,49 @@ fn compile_predicate_in_context(
             "NOT ({})",
             compile_predicate_in_context(schema, context, inner, binds, next_bind)?
         )),
+        Predicate::StudyDateTimeUtc(_) => Err(IndexError::invalid_query(
+            "UTC study date/time matching is not supported inside sequence items",
+        )),
         Predicate::Attribute(path, MatchingRule::Sequence(sequence)) => {
             compile_sequence_matching(schema, context.clone(), path, sequence, binds, next_bind)
         }
         Predicate::Attribute(path, rule) => {
-            let value_sql = if context.allow_mapped {
-                if let Some(mapping) = schema.attribute_for(path) {
-                    format!("{}::text", mapped_column_sql(mapping.table, mapping.column))
-                } else {
+            let mapping = schema.attribute_for(path).filter(|_| context.allow_mapped);
+            let (value_sql, rule) = if let Some(mapping) = mapping {
+                mapped_match(mapping, rule)
+            } else {
+                (
                     json_extract_path_text_sql(
                         &context.expr,
                         &json_value_tokens(path, context.wrapped, false)?,
-                    )
-                }
-            } else {
-                json_extract_path_text_sql(
-                    &context.expr,
-                    &json_value_tokens(path, context.wrapped, false)?,
+                    ),
+                    rule.clone(),
                 )
             };
 
-            compile_matching_rule(&value_sql, path_vr(path), rule, binds, next_bind)
+            compile_matching_rule(&value_sql, path_vr(path), &rule, binds, next_bind)
         }
     }
 }
 
+/// Translates DICOM `*` and `?` wildcards into a LIKE pattern, escaping any
+/// literal `%`, `_` or `\` in the value.
+fn like_pattern(value: &str) -> String {
+    let mut pattern = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '*' => pattern.push('%'),
+            '?' => pattern.push('_'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(ch);
+            }
+            _ => pattern.push(ch),
+        }
+    }
+    pattern
+}
+
 fn bind_text_predicate(
     value_sql: &str,
     operator: &str,
@@ -594,6 +668,86 @@ fn mapped_column_sql(table: TableId, column: &str) -> String {
     format!("{alias}.{column}")
 }
 
+/// Person names are matched and ordered on their folded `_search` companion
+/// column, with query values folded the same way in Rust, so accents and
+/// case compare identically on every backend. Responses still project the
+/// original column.
+fn mapped_match(mapping: &AttributeMapping, rule: &MatchingRule) -> (String, MatchingRule) {
+    match mapping.vr {
+        MappedVr::PersonName => (
+            person_name_search_sql(mapping),
+            rule.map_values(fold_person_name),
+        ),
+        _ => (
+            format!("{}::text", mapped_column_sql(mapping.table, mapping.column)),
+            rule.clone(),
+        ),
+    }
+}
+
+fn mapped_order_sql(mapping: &AttributeMapping) -> String {
+    match mapping.vr {
+        MappedVr::PersonName => person_name_search_sql(mapping),
+        _ => mapped_column_sql(mapping.table, mapping.column),
+    }
+}
+
+fn person_name_search_sql(mapping: &AttributeMapping) -> String {
+    format!(
+        "{}_search",
+        mapped_column_sql(mapping.table, mapping.column)
+    )
+}
+
+/// Study attributes derived at query time from the study's current series or
+/// instances rather than stored, so re-stored objects are reflected
+/// immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StudyAggregate {
+    ModalitiesInStudy,
+    SopClassesInStudy,
+}
+
+impl StudyAggregate {
+    fn for_path(path: &AttributePath) -> Option<Self> {
+        match top_level_tag(path)? {
+            tags::MODALITIES_IN_STUDY => Some(Self::ModalitiesInStudy),
+            tags::SOP_CLASSES_IN_STUDY => Some(Self::SopClassesInStudy),
+            _ => None,
+        }
+    }
+
+    fn source(self) -> (&'static str, &'static str) {
+        match self {
+            Self::ModalitiesInStudy => (SERIES.name, "modality"),
+            Self::SopClassesInStudy => (INSTANCES.name, "sop_class_uid"),
+        }
+    }
+
+    fn match_vr(self) -> VR {
+        match self {
+            Self::ModalitiesInStudy => VR::CS,
+            Self::SopClassesInStudy => VR::UI,
+        }
+    }
+
+    fn vr(self) -> &'static str {
+        match self {
+            Self::ModalitiesInStudy => "CS",
+            Self::SopClassesInStudy => "UI",
+        }
+    }
+
+    /// Distinct, sorted source values joined as one multi-valued string.
+    fn select_sql(self) -> String {
+        let (table, column) = self.source();
+        format!(
+            "(SELECT string_agg(DISTINCT agg.{column}, '\\' ORDER BY agg.{column}) FROM {table} agg WHERE agg.study_instance_uid = {}.study_instance_uid AND agg.{column} <> '')",
+            STUDIES.alias
+        )
+    }
+}
+
 fn instance_attributes_column() -> &'static str {
     "i.attributes"
 }
@@ -702,9 +856,11 @@ fn mapped_projection_body(vr: &str, value: &str) -> serde_json::Value {
             }],
         })
     } else {
+        // Mapped text VRs cannot contain a backslash, so it only ever
+        // separates values of a multi-valued projection.
         serde_json::json!({
             "vr": vr,
-            "Value": [value],
+            "Value": value.split('\\').collect::<Vec<_>>(),
         })
     }
 }
@@ -794,11 +950,13 @@ mod tests {
     use dicom_dictionary_std::tags;
     use rustcoon_index::{
         AttributePath, CatalogQuery, ItemSelector, MatchingRule, Paging,
-        PatientRootQueryRetrieveLevel, Predicate, QueryRetrieveScope, SequenceMatching,
-        SortDirection, SortKey, StudyRootQueryRetrieveLevel,
+        PatientRootQueryRetrieveLevel, Predicate, QueryRetrieveScope, RangeMatching,
+        SequenceMatching, SortDirection, SortKey, StudyRootQueryRetrieveLevel,
     };
 
-    use super::{compile_query, materialize_projection};
+    use super::{
+        BindValue, CompiledProjection, compile_query, like_pattern, materialize_projection,
+    };
     use crate::query::compile::ProjectionValue;
     use crate::schema::CatalogSchema;
 
@@ -840,7 +998,11 @@ fn compiler_uses_indexed_columns_and_jsonb_fallback_for_image_level() {
                 .sql
                 .contains("jsonb_extract_path(i.attributes, 'tag', '00080070')")
         );
-        assert!(compiled.sql.contains("ORDER BY se.series_number ASC"));
+        assert!(
+            compiled
+                .sql
+                .contains("ORDER BY se.series_number ASC, i.sop_instance_uid")
+        );
         assert_eq!(compiled.binds.len(), 4);
     }
 
@@ -903,7 +1065,11 @@ fn compiler_supports_patient_root_patient_queries() {
                 .sql
                 .contains("ORDER BY s.patient_id, s.patient_name")
         );
-        assert!(compiled.sql.contains("s.patient_name::text LIKE $1"));
+        assert!(
+            compiled
+                .sql
+                .contains("s.patient_name_search LIKE $1 ESCAPE '\\'")
+        );
     }
 
     #[test]
@@ -1038,6 +1204,87 @@ fn compiler_compiles_datetime_range_for_custom_tag_with_json_fallback() {
         );
     }
 
+    #[test]
+    fn compiler_maps_utc_study_date_time_predicate_to_study_column() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::STUDY_INSTANCE_UID)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::StudyDateTimeUtc(RangeMatching::closed(
+            "20260101000000",
+            "20260131235959",
+        )))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile utc query");
+        assert!(
+            compiled
+                .sql
+                .contains("(s.study_date_time_utc >= $1 AND s.study_date_time_utc <= $2)")
+        );
+        assert_eq!(compiled.binds.len(), 2);
+    }
+
+    #[test]
+    fn compiler_derives_modalities_in_study_from_series() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::MODALITIES_IN_STUDY)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::Attribute(
+            AttributePath::from_tag(tags::MODALITIES_IN_STUDY),
+            MatchingRule::SingleValue("CT".to_string()),
+        ))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile modalities query");
+        assert!(compiled.sql.contains(
+            "string_agg(DISTINCT agg.modality, '\\' ORDER BY agg.modality) FROM series agg WHERE agg.study_instance_uid = s.study_instance_uid"
+        ));
+        assert!(compiled.sql.contains(
+            "EXISTS (SELECT 1 FROM series agg WHERE agg.study_instance_uid = s.study_instance_uid AND agg.modality = $1)"
+        ));
+
+        let projection = materialize_projection(&[ProjectionValue::Mapped {
+            path: AttributePath::from_tag(tags::MODALITIES_IN_STUDY),
+            vr: "CS",
+            value: Some("CT\\SR".to_string()),
+        }])
+        .expect("materialize");
+        let element = projection
+            .projection
+            .element(tags::MODALITIES_IN_STUDY)
+            .unwrap();
+        assert_eq!(element.value().multiplicity(), 2);
+    }
+
+    #[test]
+    fn compiler_derives_sop_classes_in_study_from_instances() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::SOP_CLASSES_IN_STUDY)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::Attribute(
+            AttributePath::from_tag(tags::SOP_CLASSES_IN_STUDY),
+            MatchingRule::UidList(vec!["1.2.3".to_string(), "1.2.4".to_string()]),
+        ))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile sop classes query");
+        assert!(compiled.sql.contains(
+            "string_agg(DISTINCT agg.sop_class_uid, '\\' ORDER BY agg.sop_class_uid) FROM instances agg"
+        ));
+        assert!(compiled.sql.contains(
+            "EXISTS (SELECT 1 FROM instances agg WHERE agg.study_instance_uid = s.study_instance_uid AND agg.sop_class_uid IN ($1, $2))"
+        ));
+    }
+
     #[test]
     fn materialize_projection_builds_projected_object() {
         let projection = materialize_projection(&[
@@ -1124,4 +1371,79 @@ fn materialize_projection_builds_nested_sequence_structure() {
             "STEP-1"
         );
     }
+
+    #[test]
+    fn compiler_binds_matching_values_instead_of_inlining_them() {
+        const HOSTILE: &str = "' OR '1'='1\\";
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::STUDY_INSTANCE_UID)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::All(vec![
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::PATIENT_ID),
+                MatchingRule::SingleValue(HOSTILE.to_string()),
+            ),
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::INSTITUTION_NAME),
+                MatchingRule::SingleValue(HOSTILE.to_string()),
+            ),
+        ]))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile study query");
+        assert!(!compiled.sql.contains("OR '1'='1"));
+        let bound = compiled
+            .binds
+            .iter()
+            .filter(|bind| matches!(bind, BindValue::Text(value) if value == HOSTILE))
+            .count();
+        assert_eq!(bound, 2);
+    }
+
+    #[test]
+    fn wildcard_matching_escapes_literal_like_characters() {
+        assert_eq!(like_pattern("DOE*"), "DOE%");
+        assert_eq!(like_pattern("A?C"), "A_C");
+        assert_eq!(like_pattern("100%_A\\*"), "100\\%\\_A\\\\%");
+    }
+
+    #[test]
+    fn person_names_match_and_sort_on_folded_search_columns() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::PATIENT_NAME)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::Attribute(
+            AttributePath::from_tag(tags::REFERRING_PHYSICIAN_NAME),
+            MatchingRule::Wildcard("müller*".to_string()),
+        ))
+        .unwrap()
+        .with_sort(vec![SortKey {
+            path: AttributePath::from_tag(tags::PATIENT_NAME),
+            direction: SortDirection::Ascending,
+        }])
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile study query");
+        assert!(
+            compiled
+                .sql
+                .contains("s.referring_physician_name_search LIKE $1 ESCAPE '\\'")
+        );
+        assert!(compiled.sql.contains("s.patient_name_search ASC"));
+        assert!(matches!(
+            compiled.binds.as_slice(),
+            [BindValue::Text(value)] if value == "MULLER%"
+        ));
+        assert!(matches!(
+            compiled.projections.as_slice(),
+            [CompiledProjection::Mapped { select_sql, .. }] if select_sql.starts_with("s.patient_name")
+                && !select_sql.contains("_search")
+        ));
+    }
 }
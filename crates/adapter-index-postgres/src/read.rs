@@ -23,6 +23,8 @@ struct StudyRowData {
     patient_name: Option<String>,
     accession_number: Option<String>,
     study_id: Option<String>,
+    instance_count: i64,
+    size_bytes: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,7 +63,14 @@ async fn get_study(
     ) -> Result<Option<CatalogStudyEntry>, IndexError> {
         let row = sqlx::query(
             r#"
-            SELECT study_instance_uid, patient_id, patient_name, accession_number, study_id
+            SELECT
+                study_instance_uid,
+                patient_id,
+                patient_name,
+                accession_number,
+                study_id,
+                instance_count,
+                size_bytes
             FROM studies
             WHERE study_instance_uid = $1
             "#,
@@ -178,6 +187,33 @@ async fn query(&self, query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, In
 
         Ok(Page::new(items, compiled.paging, None))
     }
+
+    async fn list_superseded_blobs(
+        &self,
+        sop_instance_uid: &SopInstanceUid,
+    ) -> Result<Vec<StoredObjectRef>, IndexError> {
+        let rows: Vec<(String, Option<String>, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT blob_key, blob_version, blob_size_bytes
+            FROM superseded_blobs
+            WHERE sop_instance_uid = $1
+            ORDER BY superseded_at DESC, blob_key DESC
+            "#,
+        )
+        .bind(sop_instance_uid.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| map_sqlx(IndexOperation::ListSupersededBlobs, err))?;
+
+        rows.into_iter()
+            .filter_map(|(key, version, size_bytes)| {
+                blob_ref_from_parts(Some(key), version, size_bytes).transpose()
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|err| {
+                IndexError::backend("postgres", IndexOperation::ListSupersededBlobs, err)
+            })
+    }
 }
 
 fn row_to_study_entry(row: sqlx::postgres::PgRow) -> Result<CatalogStudyEntry, IndexError> {
@@ -197,6 +233,12 @@ fn row_to_study_entry(row: sqlx::postgres::PgRow) -> Result<CatalogStudyEntry, I
         study_id: row
             .try_get::<Option<String>, _>("study_id")
             .map_err(|err| IndexError::backend("postgres", IndexOperation::GetStudy, err))?,
+        instance_count: row
+            .try_get::<i64, _>("instance_count")
+            .map_err(|err| IndexError::backend("postgres", IndexOperation::GetStudy, err))?,
+        size_bytes: row
+            .try_get::<i64, _>("size_bytes")
+            .map_err(|err| IndexError::backend("postgres", IndexOperation::GetStudy, err))?,
     })
 }
 
@@ -279,6 +321,8 @@ fn study_entry_from_data(data: StudyRowData) -> Result<CatalogStudyEntry, IndexE
             DicomPatient::new(data.patient_id, data.patient_name),
             DicomStudyMetadata::new(data.accession_number, data.study_id),
         ),
+        instance_count: data.instance_count.max(0) as u64,
+        size_bytes: data.size_bytes.max(0) as u64,
     })
 }
 
@@ -425,6 +469,8 @@ fn study_entry_from_data_builds_normalized_record() {
             patient_name: Some(" Jane Doe ".to_string()),
             accession_number: Some(" ACC-123 ".to_string()),
             study_id: Some(" STUDY-1 ".to_string()),
+            instance_count: 3,
+            size_bytes: -1,
         })
         .expect("study entry");
 
@@ -434,6 +480,8 @@ fn study_entry_from_data_builds_normalized_record() {
         );
         assert_eq!(entry.record.patient().patient_id(), Some("PAT-001"));
         assert_eq!(entry.record.metadata().accession_number(), Some("ACC-123"));
+        assert_eq!(entry.instance_count, 3);
+        assert_eq!(entry.size_bytes, 0);
     }
 
     #[test]
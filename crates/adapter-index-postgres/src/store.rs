@@ -1,7 +1,12 @@
-use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use std::str::FromStr;
+
+use rustcoon_dicom::fold_person_name;
+use rustcoon_index::study_date_time_utc;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{PgPool, Row};
 
 use crate::config::PostgresCatalogConfig;
+use crate::read::deserialize_attributes;
 use crate::schema::CatalogSchema;
 
 #[derive(Debug, Clone)]
@@ -21,7 +26,7 @@ pub fn new(pool: PgPool) -> Self {
     pub async fn connect(config: &PostgresCatalogConfig) -> Result<Self, sqlx::Error> {
         let pool = PgPoolOptions::new()
             .max_connections(config.max_connections())
-            .connect(config.connection_string())
+            .connect_with(connect_options(config)?)
             .await?;
 
         Ok(Self::new(pool))
@@ -30,6 +35,194 @@ pub async fn connect(config: &PostgresCatalogConfig) -> Result<Self, sqlx::Error
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
+
+    /// Fills derived study columns for rows written before they existed. Run
+    /// it against the primary only.
+    pub async fn run_backfills(&self) -> Result<(), sqlx::Error> {
+        self.backfill_person_name_search().await?;
+        self.backfill_study_date_time_utc().await?;
+        Ok(())
+    }
+
+    /// Fills the UTC study timestamp for studies written before it existed,
+    /// from the latest instance document of each study. Runs in batches once;
+    /// returns the number of studies updated.
+    pub async fn backfill_study_date_time_utc(&self) -> Result<u64, sqlx::Error> {
+        const NAME: &str = "study_date_time_utc";
+        if backfill_completed(&self.pool, NAME).await? {
+            return Ok(0);
+        }
+
+        let mut updated = 0;
+        let mut after = String::new();
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    s.study_instance_uid,
+                    (
+                        SELECT i.attributes
+                        FROM instances i
+                        WHERE i.study_instance_uid = s.study_instance_uid
+                        ORDER BY i.updated_at DESC
+                        LIMIT 1
+                    ) AS attributes
+                FROM studies s
+                WHERE s.study_date_time_utc IS NULL AND s.study_instance_uid > $1
+                ORDER BY s.study_instance_uid
+                LIMIT $2
+                "#,
+            )
+            .bind(&after)
+            .bind(BACKFILL_BATCH_SIZE)
+            .fetch_all(&self.pool)
+            .await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after = last.try_get("study_instance_uid")?;
+
+            let mut tx = self.pool.begin().await?;
+            for row in &rows {
+                let Some(value) = row
+                    .try_get::<Option<serde_json::Value>, _>("attributes")?
+                    .and_then(|attributes| deserialize_attributes(attributes).ok())
+                    .and_then(|attributes| study_date_time_utc(&attributes))
+                else {
+                    continue;
+                };
+                sqlx::query(
+                    "UPDATE studies SET study_date_time_utc = $1 WHERE study_instance_uid = $2",
+                )
+                .bind(value)
+                .bind(row.try_get::<String, _>("study_instance_uid")?)
+                .execute(&mut *tx)
+                .await?;
+                updated += 1;
+            }
+            tx.commit().await?;
+        }
+
+        mark_backfill_completed(&self.pool, NAME).await?;
+        Ok(updated)
+    }
+
+    /// Recomputes the folded person-name search columns for studies written
+    /// before they existed or under an earlier folding. Folding happens in
+    /// Rust so it matches write-time values exactly. Runs in batches once;
+    /// returns the number of studies updated.
+    pub async fn backfill_person_name_search(&self) -> Result<u64, sqlx::Error> {
+        const NAME: &str = "person_name_search";
+        if backfill_completed(&self.pool, NAME).await? {
+            return Ok(0);
+        }
+
+        let mut updated = 0;
+        let mut after = String::new();
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    study_instance_uid,
+                    patient_name,
+                    referring_physician_name,
+                    patient_name_search,
+                    referring_physician_name_search
+                FROM studies
+                WHERE (patient_name IS NOT NULL OR referring_physician_name IS NOT NULL)
+                  AND study_instance_uid > $1
+                ORDER BY study_instance_uid
+                LIMIT $2
+                "#,
+            )
+            .bind(&after)
+            .bind(BACKFILL_BATCH_SIZE)
+            .fetch_all(&self.pool)
+            .await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after = last.try_get("study_instance_uid")?;
+
+            let mut tx = self.pool.begin().await?;
+            for row in &rows {
+                let fold = |column| {
+                    row.try_get::<Option<String>, _>(column)
+                        .map(|name| name.as_deref().map(fold_person_name))
+                };
+                let patient_name_search = fold("patient_name")?;
+                let referring_physician_name_search = fold("referring_physician_name")?;
+                if patient_name_search == row.try_get("patient_name_search")?
+                    && referring_physician_name_search
+                        == row.try_get("referring_physician_name_search")?
+                {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    UPDATE studies
+                    SET patient_name_search = $1, referring_physician_name_search = $2
+                    WHERE study_instance_uid = $3
+                    "#,
+                )
+                .bind(patient_name_search)
+                .bind(referring_physician_name_search)
+                .bind(row.try_get::<String, _>("study_instance_uid")?)
+                .execute(&mut *tx)
+                .await?;
+                updated += 1;
+            }
+            tx.commit().await?;
+        }
+
+        mark_backfill_completed(&self.pool, NAME).await?;
+        Ok(updated)
+    }
+}
+
+const BACKFILL_BATCH_SIZE: i64 = 500;
+
+async fn backfill_completed(pool: &PgPool, name: &str) -> Result<bool, sqlx::Error> {
+    Ok(
+        sqlx::query("SELECT 1 FROM catalog_backfills WHERE name = $1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?
+            .is_some(),
+    )
+}
+
+async fn mark_backfill_completed(pool: &PgPool, name: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO catalog_backfills (name) VALUES ($1) ON CONFLICT (name) DO NOTHING")
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+fn connect_options(config: &PostgresCatalogConfig) -> Result<PgConnectOptions, sqlx::Error> {
+    let options = PgConnectOptions::from_str(config.connection_string())?;
+    let Some(schema) = config.schema() else {
+        return Ok(options);
+    };
+    if !is_plain_identifier(schema) {
+        return Err(sqlx::Error::Configuration(
+            format!("invalid Postgres catalog schema name: {schema:?}").into(),
+        ));
+    }
+
+    Ok(options.options([("search_path", schema)]))
+}
+
+/// Accepts unquoted Postgres identifiers only, so the name can be passed
+/// through the startup `options` string without escaping.
+fn is_plain_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_lowercase() || first == '_')
+        && chars.all(|rest| rest.is_ascii_lowercase() || rest.is_ascii_digit() || rest == '_')
+        && value.len() <= 63
 }
 
 #[cfg(test)]
@@ -37,7 +230,7 @@ mod tests {
     use sqlx::postgres::PgPoolOptions;
 
     use crate::config::PostgresCatalogConfig;
-    use crate::store::PostgresCatalogStore;
+    use crate::store::{PostgresCatalogStore, connect_options};
 
     #[tokio::test]
     async fn new_initializes_store_with_pool() {
@@ -56,4 +249,30 @@ async fn connect_rejects_invalid_connection_string() {
 
         assert!(PostgresCatalogStore::connect(&config).await.is_err());
     }
+
+    #[test]
+    fn connect_options_set_search_path_for_schema() {
+        let config =
+            PostgresCatalogConfig::new("postgres://localhost/shared").with_schema("rustcoon");
+
+        let options = connect_options(&config).expect("connect options");
+
+        assert_eq!(options.get_options(), Some("-c search_path=rustcoon"));
+        assert_eq!(
+            connect_options(&PostgresCatalogConfig::new("postgres://localhost/shared"))
+                .expect("connect options")
+                .get_options(),
+            None
+        );
+    }
+
+    #[test]
+    fn connect_options_reject_unsafe_schema_names() {
+        for schema in ["", "Rustcoon", "pacs; drop", "1pacs", "pacs,public"] {
+            let config =
+                PostgresCatalogConfig::new("postgres://localhost/shared").with_schema(schema);
+
+            assert!(connect_options(&config).is_err(), "{schema:?}");
+        }
+    }
 }
@@ -1,15 +1,26 @@
 use async_trait::async_trait;
 use dicom_dictionary_std::tags;
+use rustcoon_dicom::{DicomInstanceIdentity, fold_person_name};
 use rustcoon_index::{
     CatalogUpsertOutcome, CatalogWriteStore, IndexError, IndexOperation, InstanceUpsertRequest,
-    StoredObjectRef,
+    StoredObjectRef, StudyQuota, check_hierarchy_unchanged, promoted_text, study_date_time_utc,
+    without_receipt,
 };
 use sqlx::Row;
 
 use crate::error::map_sqlx;
-use crate::read::serialize_attributes;
+use crate::read::{deserialize_attributes, serialize_attributes};
 use crate::store::PostgresCatalogStore;
 
+const ADJUST_STUDY_COUNTERS_SQL: &str = r#"
+    UPDATE studies
+    SET
+        instance_count = instance_count + $2,
+        size_bytes = size_bytes + $3
+    WHERE study_instance_uid = $1
+    RETURNING instance_count, size_bytes
+"#;
+
 #[derive(Debug, Clone, PartialEq)]
 struct DesiredInstanceState {
     sop_class_uid: String,
@@ -51,14 +62,12 @@ async fn upsert_instance(
         let study = request.record.study();
         let series = request.record.series();
         let instance = request.record.instance();
-        let attributes = serialize_attributes(&request.attributes).map_err(|err| {
-            IndexError::backend(
-                "postgres",
-                IndexOperation::UpsertInstance,
-                std::io::Error::other(err.to_string()),
-            )
-        })?;
+        let attributes = serialize_document(&request.attributes, IndexOperation::UpsertInstance)?;
+
+        ensure_hierarchy_unchanged(&mut tx, identity, IndexOperation::UpsertInstance).await?;
 
+        let referring_physician_name =
+            promoted_text(&request.attributes, tags::REFERRING_PHYSICIAN_NAME);
         sqlx::query(
             r#"
             INSERT INTO studies (
@@ -66,14 +75,24 @@ async fn upsert_instance(
                 patient_id,
                 patient_name,
                 accession_number,
-                study_id
+                study_id,
+                study_date_time_utc,
+                issuer_of_patient_id,
+                referring_physician_name,
+                patient_name_search,
+                referring_physician_name_search
             )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ON CONFLICT (study_instance_uid) DO UPDATE SET
                 patient_id = EXCLUDED.patient_id,
                 patient_name = EXCLUDED.patient_name,
                 accession_number = EXCLUDED.accession_number,
-                study_id = EXCLUDED.study_id
+                study_id = EXCLUDED.study_id,
+                study_date_time_utc = EXCLUDED.study_date_time_utc,
+                issuer_of_patient_id = EXCLUDED.issuer_of_patient_id,
+                referring_physician_name = EXCLUDED.referring_physician_name,
+                patient_name_search = EXCLUDED.patient_name_search,
+                referring_physician_name_search = EXCLUDED.referring_physician_name_search
             "#,
         )
         .bind(identity.study_instance_uid().as_str())
@@ -81,6 +100,14 @@ async fn upsert_instance(
         .bind(patient.patient_name())
         .bind(study.accession_number())
         .bind(study.study_id())
+        .bind(study_date_time_utc(&request.attributes))
+        .bind(promoted_text(
+            &request.attributes,
+            tags::ISSUER_OF_PATIENT_ID,
+        ))
+        .bind(&referring_physician_name)
+        .bind(patient.patient_name().map(fold_person_name))
+        .bind(referring_physician_name.as_deref().map(fold_person_name))
         .execute(&mut *tx)
         .await
         .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
@@ -91,64 +118,49 @@ async fn upsert_instance(
                 series_instance_uid,
                 study_instance_uid,
                 modality,
-                series_number
+                series_number,
+                body_part_examined,
+                laterality
             )
-            VALUES ($1, $2, $3, $4)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT (series_instance_uid) DO UPDATE SET
                 study_instance_uid = EXCLUDED.study_instance_uid,
                 modality = EXCLUDED.modality,
-                series_number = EXCLUDED.series_number
+                series_number = EXCLUDED.series_number,
+                body_part_examined = EXCLUDED.body_part_examined,
+                laterality = EXCLUDED.laterality
             "#,
         )
         .bind(identity.series_instance_uid().as_str())
         .bind(identity.study_instance_uid().as_str())
         .bind(series.modality())
         .bind(series.series_number().map(|value| value as i32))
+        .bind(promoted_text(&request.attributes, tags::BODY_PART_EXAMINED))
+        .bind(promoted_text(&request.attributes, tags::LATERALITY))
         .execute(&mut *tx)
         .await
         .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
 
-        let existing = sqlx::query(
-            r#"
-            SELECT
-                sop_class_uid,
-                instance_number,
-                acquisition_date_time,
-                transfer_syntax_uid,
-                attributes,
-                blob_key,
-                blob_version,
-                blob_size_bytes
-            FROM instances
-            WHERE sop_instance_uid = $1
-            "#,
-        )
-        .bind(identity.sop_instance_uid().as_str())
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
+        let existing =
+            select_existing_instance(&mut tx, identity, IndexOperation::UpsertInstance).await?;
 
-        let blob_key = request.blob.as_ref().map(|blob| blob.key.to_string());
-        let blob_version = request.blob.as_ref().and_then(|blob| blob.version.clone());
-        let blob_size = request
-            .blob
-            .as_ref()
-            .and_then(|blob| blob.size_bytes)
-            .map(|value| value as i64);
+        let (blob_key, blob_version, blob_size) = blob_columns(request.blob.as_ref());
         let desired_state = DesiredInstanceState::from_request(
             &request,
-            attributes.clone(),
+            comparable_attributes(attributes.clone()),
             blob_key.clone(),
             blob_version.clone(),
             blob_size,
         );
 
-        let outcome = if let Some(row) = existing {
-            let unchanged = ExistingInstanceState::try_from_row(&row)
-                .map(|existing| existing.matches(&desired_state))
-                .unwrap_or(false);
+        let previous_blob = existing
+            .as_ref()
+            .map(PreviousBlob::try_from_row)
+            .transpose()
+            .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
 
-            if unchanged {
+        let outcome = if let Some(row) = existing {
+            if ExistingInstanceState::matches_row(&row, &desired_state) {
                 CatalogUpsertOutcome::Unchanged
             } else {
                 sqlx::query(
@@ -223,6 +235,32 @@ async fn upsert_instance(
             CatalogUpsertOutcome::Created
         };
 
+        if outcome != CatalogUpsertOutcome::Unchanged {
+            let (instance_delta, released_bytes) = match &previous_blob {
+                Some(previous_blob) => (
+                    0,
+                    replace_blob(
+                        &mut tx,
+                        identity.sop_instance_uid().as_str(),
+                        identity.study_instance_uid().as_str(),
+                        previous_blob,
+                        desired_state.blob_key.as_deref(),
+                        IndexOperation::UpsertInstance,
+                    )
+                    .await?,
+                ),
+                None => (1, 0),
+            };
+            adjust_study_counters(
+                &mut tx,
+                identity.study_instance_uid().as_str(),
+                (instance_delta, blob_size.unwrap_or(0) - released_bytes),
+                request.study_quota.as_ref(),
+                IndexOperation::UpsertInstance,
+            )
+            .await?;
+        }
+
         tx.commit()
             .await
             .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
@@ -230,12 +268,77 @@ async fn upsert_instance(
         Ok(outcome)
     }
 
+    async fn preview_upsert(
+        &self,
+        request: &InstanceUpsertRequest,
+    ) -> Result<CatalogUpsertOutcome, IndexError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| map_sqlx(IndexOperation::PreviewUpsert, err))?;
+        let identity = request.record.identity();
+        ensure_hierarchy_unchanged(&mut tx, identity, IndexOperation::PreviewUpsert).await?;
+        let existing =
+            select_existing_instance(&mut tx, identity, IndexOperation::PreviewUpsert).await?;
+        let Some(row) = existing else {
+            return Ok(CatalogUpsertOutcome::Created);
+        };
+
+        let attributes = serialize_document(&request.attributes, IndexOperation::PreviewUpsert)?;
+        let (blob_key, blob_version, blob_size) = blob_columns(request.blob.as_ref());
+        let desired_state = DesiredInstanceState::from_request(
+            request,
+            comparable_attributes(attributes),
+            blob_key,
+            blob_version,
+            blob_size,
+        );
+        Ok(
+            if ExistingInstanceState::matches_row(&row, &desired_state) {
+                CatalogUpsertOutcome::Unchanged
+            } else {
+                CatalogUpsertOutcome::Updated
+            },
+        )
+    }
+
     async fn attach_blob(
         &self,
         identity: &rustcoon_dicom::DicomInstanceIdentity,
         blob: StoredObjectRef,
     ) -> Result<(), IndexError> {
-        let result = sqlx::query(
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
+
+        let existing = sqlx::query(
+            r#"
+            SELECT study_instance_uid, blob_key, blob_version, blob_size_bytes
+            FROM instances
+            WHERE sop_instance_uid = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(identity.sop_instance_uid().as_str())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
+        let Some(row) = existing else {
+            return Err(IndexError::instance_not_found(
+                identity.sop_instance_uid().clone(),
+            ));
+        };
+        let (study_instance_uid, previous_blob) = row
+            .try_get::<String, _>("study_instance_uid")
+            .and_then(|study| Ok((study, PreviousBlob::try_from_row(&row)?)))
+            .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
+        let blob_key = blob.key.to_string();
+
+        let size_bytes = blob.size_bytes.map(|value| value as i64);
+        sqlx::query(
             r#"
             UPDATE instances
             SET
@@ -247,21 +350,229 @@ async fn attach_blob(
             "#,
         )
         .bind(identity.sop_instance_uid().as_str())
-        .bind(blob.key.to_string())
+        .bind(&blob_key)
         .bind(blob.version)
-        .bind(blob.size_bytes.map(|value| value as i64))
-        .execute(&self.pool)
+        .bind(size_bytes)
+        .execute(&mut *tx)
         .await
         .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
 
-        if result.rows_affected() == 0 {
-            return Err(IndexError::instance_not_found(
-                identity.sop_instance_uid().clone(),
-            ));
+        let released_bytes = replace_blob(
+            &mut tx,
+            identity.sop_instance_uid().as_str(),
+            &study_instance_uid,
+            &previous_blob,
+            Some(&blob_key),
+            IndexOperation::AttachBlob,
+        )
+        .await?;
+        adjust_study_counters(
+            &mut tx,
+            &study_instance_uid,
+            (0, size_bytes.unwrap_or(0) - released_bytes),
+            None,
+            IndexOperation::AttachBlob,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))
+    }
+}
+
+/// Blob an existing instance referenced before this write.
+#[derive(Debug, Clone, PartialEq)]
+struct PreviousBlob {
+    key: Option<String>,
+    version: Option<String>,
+    size_bytes: Option<i64>,
+}
+
+impl PreviousBlob {
+    fn try_from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            key: row.try_get::<Option<String>, _>("blob_key")?,
+            version: row.try_get::<Option<String>, _>("blob_version")?,
+            size_bytes: row.try_get::<Option<i64>, _>("blob_size_bytes")?,
+        })
+    }
+}
+
+/// Tracks an instance moving from `previous` to the blob at `next_key`. A
+/// previous blob under another key is kept in `superseded_blobs` rather than
+/// orphaned, and a superseded blob that becomes current again leaves it.
+/// Returns the bytes no longer counted against the study.
+async fn replace_blob(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    sop_instance_uid: &str,
+    study_instance_uid: &str,
+    previous: &PreviousBlob,
+    next_key: Option<&str>,
+    operation: IndexOperation,
+) -> Result<i64, IndexError> {
+    let reactivated_bytes = match next_key {
+        Some(next_key) if previous.key.as_deref() != Some(next_key) => {
+            let reactivated: Option<Option<i64>> = sqlx::query_scalar(
+                "DELETE FROM superseded_blobs WHERE blob_key = $1 RETURNING blob_size_bytes",
+            )
+            .bind(next_key)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|err| map_sqlx(operation, err))?;
+            reactivated.flatten().unwrap_or(0)
         }
+        _ => 0,
+    };
 
-        Ok(())
+    let Some(previous_key) = previous.key.as_deref() else {
+        return Ok(reactivated_bytes);
+    };
+    if next_key == Some(previous_key) {
+        return Ok(reactivated_bytes + previous.size_bytes.unwrap_or(0));
     }
+
+    sqlx::query(
+        r#"
+        INSERT INTO superseded_blobs (
+            blob_key,
+            sop_instance_uid,
+            study_instance_uid,
+            blob_version,
+            blob_size_bytes
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (blob_key) DO NOTHING
+        "#,
+    )
+    .bind(previous_key)
+    .bind(sop_instance_uid)
+    .bind(study_instance_uid)
+    .bind(&previous.version)
+    .bind(previous.size_bytes)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| map_sqlx(operation, err))?;
+
+    Ok(reactivated_bytes)
+}
+
+/// Applies instance-count and size deltas to a study's maintained totals and
+/// checks the updated totals against `quota`.
+async fn adjust_study_counters(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    study_instance_uid: &str,
+    (instance_delta, size_delta): (i64, i64),
+    quota: Option<&StudyQuota>,
+    operation: IndexOperation,
+) -> Result<(), IndexError> {
+    if instance_delta == 0 && size_delta == 0 {
+        return Ok(());
+    }
+
+    let totals: Option<(i64, i64)> = sqlx::query_as(ADJUST_STUDY_COUNTERS_SQL)
+        .bind(study_instance_uid)
+        .bind(instance_delta)
+        .bind(size_delta)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|err| map_sqlx(operation, err))?;
+
+    match (quota, totals) {
+        (Some(quota), Some((instance_count, size_bytes))) => quota.check(
+            study_instance_uid,
+            (instance_count.max(0) as u64, size_bytes.max(0) as u64),
+            (instance_delta, size_delta),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Loads the recorded parents of the incoming series and instance; the
+/// upserts below would otherwise re-parent them silently.
+async fn ensure_hierarchy_unchanged(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    identity: &DicomInstanceIdentity,
+    operation: IndexOperation,
+) -> Result<(), IndexError> {
+    let series_study: Option<String> =
+        sqlx::query_scalar("SELECT study_instance_uid FROM series WHERE series_instance_uid = $1")
+            .bind(identity.series_instance_uid().as_str())
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|err| map_sqlx(operation, err))?;
+    let instance_parents: Option<(String, String)> = sqlx::query_as(
+        "SELECT study_instance_uid, series_instance_uid FROM instances WHERE sop_instance_uid = $1",
+    )
+    .bind(identity.sop_instance_uid().as_str())
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|err| map_sqlx(operation, err))?;
+
+    check_hierarchy_unchanged(
+        identity,
+        series_study.as_deref(),
+        instance_parents
+            .as_ref()
+            .map(|(study, series)| (study.as_str(), series.as_str())),
+    )
+}
+
+async fn select_existing_instance(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    identity: &DicomInstanceIdentity,
+    operation: IndexOperation,
+) -> Result<Option<sqlx::postgres::PgRow>, IndexError> {
+    sqlx::query(
+        r#"
+        SELECT
+            sop_class_uid,
+            instance_number,
+            acquisition_date_time,
+            transfer_syntax_uid,
+            attributes,
+            blob_key,
+            blob_version,
+            blob_size_bytes
+        FROM instances
+        WHERE sop_instance_uid = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(identity.sop_instance_uid().as_str())
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|err| map_sqlx(operation, err))
+}
+
+fn serialize_document(
+    attributes: &rustcoon_index::DicomAttributeDocument,
+    operation: IndexOperation,
+) -> Result<serde_json::Value, IndexError> {
+    serialize_attributes(attributes).map_err(|err| {
+        IndexError::backend(
+            "postgres",
+            operation,
+            std::io::Error::other(err.to_string()),
+        )
+    })
+}
+
+fn blob_columns(blob: Option<&StoredObjectRef>) -> (Option<String>, Option<String>, Option<i64>) {
+    (
+        blob.map(|blob| blob.key.to_string()),
+        blob.and_then(|blob| blob.version.clone()),
+        blob.and_then(|blob| blob.size_bytes)
+            .map(|value| value as i64),
+    )
+}
+
+/// Stored document as compared across deliveries: the receipt block records
+/// the delivery itself, so it never makes a re-store count as a change.
+fn comparable_attributes(attributes: serde_json::Value) -> serde_json::Value {
+    deserialize_attributes(attributes.clone())
+        .and_then(|document| serialize_attributes(&without_receipt(&document)))
+        .unwrap_or(attributes)
 }
 
 impl DesiredInstanceState {
@@ -311,13 +622,17 @@ fn try_from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
             instance_number: row.try_get::<Option<i32>, _>("instance_number")?,
             acquisition_date_time: row.try_get::<Option<String>, _>("acquisition_date_time")?,
             transfer_syntax_uid: row.try_get::<Option<String>, _>("transfer_syntax_uid")?,
-            attributes: row.try_get::<serde_json::Value, _>("attributes")?,
+            attributes: comparable_attributes(row.try_get::<serde_json::Value, _>("attributes")?),
             blob_key: row.try_get::<Option<String>, _>("blob_key")?,
             blob_version: row.try_get::<Option<String>, _>("blob_version")?,
             blob_size_bytes: row.try_get::<Option<i64>, _>("blob_size_bytes")?,
         })
     }
 
+    fn matches_row(row: &sqlx::postgres::PgRow, desired: &DesiredInstanceState) -> bool {
+        Self::try_from_row(row).is_ok_and(|existing| existing.matches(desired))
+    }
+
     fn matches(&self, desired: &DesiredInstanceState) -> bool {
         self.sop_class_uid == desired.sop_class_uid
             && self.instance_number == desired.instance_number
@@ -2,6 +2,7 @@
 pub struct SqliteCatalogConfig {
     connection_string: String,
     max_connections: u32,
+    auto_migrate: bool,
 }
 
 impl SqliteCatalogConfig {
@@ -9,6 +10,7 @@ pub fn new(connection_string: impl Into<String>) -> Self {
         Self {
             connection_string: connection_string.into(),
             max_connections: 1,
+            auto_migrate: true,
         }
     }
 
@@ -17,6 +19,13 @@ pub fn with_max_connections(mut self, max_connections: u32) -> Self {
         self
     }
 
+    /// When disabled, connecting fails on pending or drifted migrations
+    /// instead of applying them.
+    pub fn with_auto_migrate(mut self, auto_migrate: bool) -> Self {
+        self.auto_migrate = auto_migrate;
+        self
+    }
+
     pub fn connection_string(&self) -> &str {
         &self.connection_string
     }
@@ -24,6 +33,10 @@ pub fn connection_string(&self) -> &str {
     pub fn max_connections(&self) -> u32 {
         self.max_connections
     }
+
+    pub fn auto_migrate(&self) -> bool {
+        self.auto_migrate
+    }
 }
 
 #[cfg(test)]
@@ -36,5 +49,7 @@ fn config_builder_clamps_pool_size() {
 
         assert_eq!(config.connection_string(), "sqlite://catalog.sqlite");
         assert_eq!(config.max_connections(), 1);
+        assert!(config.auto_migrate());
+        assert!(!config.with_auto_migrate(false).auto_migrate());
     }
 }
@@ -1,14 +1,17 @@
 use dicom_core::VR;
 use dicom_core::dictionary::{DataDictionary, DataDictionaryEntry};
-use dicom_dictionary_std::StandardDataDictionary;
+use dicom_dictionary_std::{StandardDataDictionary, tags};
 use dicom_object::InMemDicomObject;
+use rustcoon_dicom::fold_person_name;
 use rustcoon_index::{
     AttributePath, AttributePathSegment, CatalogQuery, CatalogQueryEntry, IndexError, ItemSelector,
     MatchingRule, Paging, PatientRootQueryRetrieveLevel, Predicate, QueryRetrieveScope,
     SequenceMatching, SortDirection, SortKey, StudyRootQueryRetrieveLevel,
 };
 
-use crate::schema::{CatalogSchema, TableId, format_tag_key};
+use crate::schema::{
+    AttributeMapping, CatalogSchema, MappedVr, TableId, format_tag_key, top_level_tag,
+};
 use crate::schema::{INSTANCES, SERIES, STUDIES};
 
 #[derive(Debug, Clone)]
@@ -81,13 +84,15 @@ pub(crate) fn compile_query(
         .map(|predicate| compile_predicate(schema, predicate, &mut binds, &mut next_bind))
         .transpose()?;
 
-    let user_sort_exprs = compile_sort(schema, query.sort())?;
     let partition_exprs = distinct_partition_exprs(level);
-    let order_exprs = if user_sort_exprs.is_empty() {
-        partition_exprs.clone()
-    } else {
-        user_sort_exprs.clone()
-    };
+    // Identity keys always close the ordering so equal sort values, and
+    // queries without a sort, page the same way on every request.
+    let mut order_exprs = compile_sort(schema, query.sort())?;
+    order_exprs.extend(
+        stable_order_exprs(level)
+            .into_iter()
+            .map(|sql| (sql, "ASC")),
+    );
 
     let projection_select = projections
         .iter()
@@ -103,10 +108,18 @@ pub(crate) fn compile_query(
     let order_select = order_exprs
         .iter()
         .enumerate()
-        .map(|(index, sql)| format!("{sql} AS o_{index}"))
+        .map(|(index, (sql, _))| format!("{sql} AS o_{index}"))
+        .collect::<Vec<_>>();
+    // The ranking window runs over the base CTE, so partition keys must be
+    // selected there instead of referencing the joined table aliases.
+    let partition_select = partition_exprs
+        .iter()
+        .enumerate()
+        .map(|(index, sql)| format!("{sql} AS d_{index}"))
         .collect::<Vec<_>>();
-    let mut select_items = projection_select.clone();
-    select_items.extend(order_select.clone());
+    let mut select_items = projection_select;
+    select_items.extend(order_select);
+    select_items.extend(partition_select);
 
     let mut base_sql = format!(
         "SELECT {} FROM {} {} JOIN {} {} ON {}.series_instance_uid = {}.series_instance_uid JOIN {} {} ON {}.study_instance_uid = {}.study_instance_uid",
@@ -135,10 +148,10 @@ pub(crate) fn compile_query(
             | CompiledProjection::JsonBody { alias, .. } => alias.clone(),
         })
         .collect::<Vec<_>>();
-    let order_aliases = order_select
+    let order_aliases = order_exprs
         .iter()
         .enumerate()
-        .map(|(index, _)| format!("o_{index}"))
+        .map(|(index, (_, direction))| format!("o_{index} {direction}"))
         .collect::<Vec<_>>();
 
     let mut sql = if partition_exprs.is_empty() {
@@ -147,8 +160,11 @@ pub(crate) fn compile_query(
             projection_aliases.join(", ")
         )
     } else {
-        let row_number_order = order_exprs.join(", ");
-        let partition_expr = partition_exprs.join(", ");
+        let row_number_order = order_aliases.join(", ");
+        let partition_expr = (0..partition_exprs.len())
+            .map(|index| format!("d_{index}"))
+            .collect::<Vec<_>>()
+            .join(", ");
         format!(
             "WITH base AS ({base_sql}), ranked AS (SELECT base.*, ROW_NUMBER() OVER (PARTITION BY {partition_expr} ORDER BY {row_number_order}) AS rn FROM base) SELECT {} FROM ranked WHERE rn = 1",
             projection_aliases.join(", ")
@@ -199,6 +215,13 @@ fn distinct_partition_exprs(level: ResultLevel) -> Vec<String> {
     }
 }
 
+fn stable_order_exprs(level: ResultLevel) -> Vec<String> {
+    match level {
+        ResultLevel::Image => vec!["i.sop_instance_uid".to_string()],
+        _ => distinct_partition_exprs(level),
+    }
+}
+
 fn compile_projection(
     schema: &CatalogSchema,
     path: &AttributePath,
@@ -206,13 +229,19 @@ fn compile_projection(
 ) -> Result<CompiledProjection, IndexError> {
     let alias = format!("p_{index}");
 
+    if let Some(aggregate) = StudyAggregate::for_path(path) {
+        return Ok(CompiledProjection::Mapped {
+            path: path.clone(),
+            select_sql: aggregate.select_sql(),
+            alias,
+            vr: aggregate.vr(),
+        });
+    }
+
     if let Some(mapping) = schema.attribute_for(path) {
         return Ok(CompiledProjection::Mapped {
             path: path.clone(),
-            select_sql: format!(
-                "CAST({} AS TEXT)",
-                mapped_column_sql(mapping.table, mapping.column)
-            ),
+            select_sql: mapped_value_sql(mapping),
             alias,
             vr: mapping.vr.dicom_json_vr(),
         });
@@ -228,7 +257,10 @@ fn compile_projection(
     })
 }
 
-fn compile_sort(schema: &CatalogSchema, sort: &[SortKey]) -> Result<Vec<String>, IndexError> {
+fn compile_sort(
+    schema: &CatalogSchema,
+    sort: &[SortKey],
+) -> Result<Vec<(String, &'static str)>, IndexError> {
     let mut order_sql = Vec::new();
 
     for SortKey { path, direction } in sort {
@@ -237,20 +269,22 @@ fn compile_sort(schema: &CatalogSchema, sort: &[SortKey]) -> Result<Vec<String>,
             SortDirection::Descending => "DESC",
         };
 
+        if let Some(aggregate) = StudyAggregate::for_path(path) {
+            order_sql.push((aggregate.select_sql(), direction));
+            continue;
+        }
+
         if let Some(mapping) = schema.attribute_for(path) {
-            order_sql.push(format!(
-                "{} {direction}",
-                mapped_column_sql(mapping.table, mapping.column)
-            ));
+            order_sql.push((mapped_order_sql(mapping), direction));
             continue;
         }
 
-        order_sql.push(format!(
-            "{} {direction}",
+        order_sql.push((
             json_extract_path_text_sql(
                 instance_attributes_column(),
                 &json_value_path(path, true, false)?,
-            )
+            ),
+            direction,
         ));
     }
 
@@ -270,6 +304,12 @@ fn compile_predicate(
             "NOT ({})",
             compile_predicate(schema, inner, binds, next_bind)?
         )),
+        Predicate::StudyDateTimeUtc(range) => Ok(compile_text_range(
+            &mapped_column_sql(TableId::Study, "study_date_time_utc"),
+            range,
+            binds,
+            next_bind,
+        )),
         Predicate::Attribute(path, MatchingRule::Sequence(sequence)) => compile_sequence_matching(
             schema,
             DatasetContext::root(),
@@ -278,20 +318,40 @@ fn compile_predicate(
             binds,
             next_bind,
         ),
+        Predicate::Attribute(path, MatchingRule::Universal)
+            if StudyAggregate::for_path(path).is_some() =>
+        {
+            Ok("TRUE".to_string())
+        }
+        Predicate::Attribute(path, rule) if StudyAggregate::for_path(path).is_some() => {
+            let aggregate = StudyAggregate::for_path(path).expect("matched aggregate");
+            let (table, column) = aggregate.source();
+            Ok(format!(
+                "EXISTS (SELECT 1 FROM {table} agg WHERE agg.study_instance_uid = {}.study_instance_uid AND {})",
+                STUDIES.alias,
+                compile_matching_rule(
+                    &format!("agg.{column}"),
+                    Some(aggregate.match_vr()),
+                    rule,
+                    binds,
+                    next_bind
+                )?
+            ))
+        }
         Predicate::Attribute(path, rule) => {
-            let value_sql = if let Some(mapping) = schema.attribute_for(path) {
-                format!(
-                    "CAST({} AS TEXT)",
-                    mapped_column_sql(mapping.table, mapping.column)
-                )
+            let (value_sql, rule) = if let Some(mapping) = schema.attribute_for(path) {
+                mapped_match(mapping, rule)
             } else {
-                json_extract_path_text_sql(
-                    instance_attributes_column(),
-                    &json_value_path(path, true, false)?,
+                (
+                    json_extract_path_text_sql(
+                        instance_attributes_column(),
+                        &json_value_path(path, true, false)?,
+                    ),
+                    rule.clone(),
                 )
             };
 
-            compile_matching_rule(&value_sql, path_vr(path), rule, binds, next_bind)
+            compile_matching_rule(&value_sql, path_vr(path), &rule, binds, next_bind)
         }
     }
 }
@@ -327,9 +387,26 @@ fn compile_matching_rule(
             Ok(bind_text_predicate(value_sql, "=", value, binds, next_bind))
         }
         MatchingRule::Wildcard(value) => {
-            let like = value.replace('*', "%").replace('?', "_");
-            Ok(bind_text_predicate(
-                value_sql, "LIKE", &like, binds, next_bind,
+            let like =
+                bind_text_predicate(value_sql, "LIKE", &like_pattern(value), binds, next_bind);
+            // SQLite does not use an index for a bound LIKE pattern; a range
+            // on the literal prefix does, with LIKE left as the residual check.
+            // Without ANALYZE statistics the planner rates a range as barely
+            // selective, so the bounds carry an explicit likelihood.
+            let prefix = value.split(['*', '?']).next().unwrap_or_default();
+            if prefix.is_empty() {
+                return Ok(format!("{like} ESCAPE '\\'"));
+            }
+            let lower = bind_text_predicate(value_sql, ">=", prefix, binds, next_bind);
+            let upper = bind_text_predicate(
+                value_sql,
+                "<",
+                &format!("{prefix}{}", char::MAX),
+                binds,
+                next_bind,
+            );
+            Ok(format!(
+                "({like} ESCAPE '\\' AND likelihood({lower}, 0.01) AND likelihood({upper}, 0.01))"
             ))
         }
         MatchingRule::Universal => Ok("TRUE".to_string()),
@@ -348,20 +425,7 @@ fn compile_matching_rule(
             if vr == Some(VR::DT) {
                 return compile_datetime_range(value_sql, range, binds, next_bind);
             }
-            let mut parts = Vec::new();
-            if let Some(start) = &range.start {
-                parts.push(bind_text_predicate(
-                    value_sql, ">=", start, binds, next_bind,
-                ));
-            }
-            if let Some(end) = &range.end {
-                parts.push(bind_text_predicate(value_sql, "<=", end, binds, next_bind));
-            }
-            if parts.is_empty() {
-                Ok("TRUE".to_string())
-            } else {
-                Ok(format!("({})", parts.join(" AND ")))
-            }
+            Ok(compile_text_range(value_sql, range, binds, next_bind))
         }
         MatchingRule::Sequence(_) => Err(IndexError::invalid_attribute_filter(
             "nested sequence matching must be compiled at the predicate layer",
@@ -369,6 +433,28 @@ fn compile_matching_rule(
     }
 }
 
+fn compile_text_range(
+    value_sql: &str,
+    range: &rustcoon_index::RangeMatching,
+    binds: &mut Vec<BindValue>,
+    next_bind: &mut usize,
+) -> String {
+    let mut parts = Vec::new();
+    if let Some(start) = &range.start {
+        parts.push(bind_text_predicate(
+            value_sql, ">=", start, binds, next_bind,
+        ));
+    }
+    if let Some(end) = &range.end {
+        parts.push(bind_text_predicate(value_sql, "<=", end, binds, next_bind));
+    }
+    if parts.is_empty() {
+        "TRUE".to_string()
+    } else {
+        format!("({})", parts.join(" AND "))
+    }
+}
+
 fn compile_datetime_range(
     value_sql: &str,
     range: &rustcoon_index::RangeMatching,
@@ -500,32 +586,47 @@ fn compile_predicate_in_context(
             "NOT ({})",
             compile_predicate_in_context(schema, context, inner, binds, next_bind)?
         )),
+        Predicate::StudyDateTimeUtc(_) => Err(IndexError::invalid_query(
+            "UTC study date/time matching is not supported inside sequence items",
+        )),
         Predicate::Attribute(path, MatchingRule::Sequence(sequence)) => {
             compile_sequence_matching(schema, context.clone(), path, sequence, binds, next_bind)
         }
         Predicate::Attribute(path, rule) => {
-            let value_sql = if context.allow_mapped {
-                if let Some(mapping) = schema.attribute_for(path) {
-                    format!(
-                        "CAST({} AS TEXT)",
-                        mapped_column_sql(mapping.table, mapping.column)
-                    )
-                } else {
+            let mapping = schema.attribute_for(path).filter(|_| context.allow_mapped);
+            let (value_sql, rule) = if let Some(mapping) = mapping {
+                mapped_match(mapping, rule)
+            } else {
+                (
                     json_extract_path_text_sql(
                         &context.expr,
                         &json_value_path(path, context.wrapped, false)?,
-                    )
-                }
-            } else {
-                json_extract_path_text_sql(
-                    &context.expr,
-                    &json_value_path(path, context.wrapped, false)?,
+                    ),
+                    rule.clone(),
                 )
             };
 
-            compile_matching_rule(&value_sql, path_vr(path), rule, binds, next_bind)
+            compile_matching_rule(&value_sql, path_vr(path), &rule, binds, next_bind)
+        }
+    }
+}
+
+/// Translates DICOM `*` and `?` wildcards into a LIKE pattern, escaping any
+/// literal `%`, `_` or `\` in the value.
+fn like_pattern(value: &str) -> String {
+    let mut pattern = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '*' => pattern.push('%'),
+            '?' => pattern.push('_'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(ch);
+            }
+            _ => pattern.push(ch),
         }
     }
+    pattern
 }
 
 fn bind_text_predicate(
@@ -567,6 +668,93 @@ fn mapped_column_sql(table: TableId, column: &str) -> String {
     format!("{alias}.{column}")
 }
 
+/// Text columns are compared as stored so their indexes stay usable; only
+/// integer-backed columns are cast for string matching.
+fn mapped_value_sql(mapping: &AttributeMapping) -> String {
+    let column = mapped_column_sql(mapping.table, mapping.column);
+    match mapping.vr {
+        MappedVr::IntegerString => format!("CAST({column} AS TEXT)"),
+        _ => column,
+    }
+}
+
+/// Person names are matched and ordered on their folded `_search` companion
+/// column, with query values folded the same way in Rust, so accents and
+/// case compare identically on every backend. Responses still project the
+/// original column.
+fn mapped_match(mapping: &AttributeMapping, rule: &MatchingRule) -> (String, MatchingRule) {
+    match mapping.vr {
+        MappedVr::PersonName => (
+            person_name_search_sql(mapping),
+            rule.map_values(fold_person_name),
+        ),
+        _ => (mapped_value_sql(mapping), rule.clone()),
+    }
+}
+
+fn mapped_order_sql(mapping: &AttributeMapping) -> String {
+    match mapping.vr {
+        MappedVr::PersonName => person_name_search_sql(mapping),
+        _ => mapped_column_sql(mapping.table, mapping.column),
+    }
+}
+
+fn person_name_search_sql(mapping: &AttributeMapping) -> String {
+    format!(
+        "{}_search",
+        mapped_column_sql(mapping.table, mapping.column)
+    )
+}
+
+/// Study attributes derived at query time from the study's current series or
+/// instances rather than stored, so re-stored objects are reflected
+/// immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StudyAggregate {
+    ModalitiesInStudy,
+    SopClassesInStudy,
+}
+
+impl StudyAggregate {
+    fn for_path(path: &AttributePath) -> Option<Self> {
+        match top_level_tag(path)? {
+            tags::MODALITIES_IN_STUDY => Some(Self::ModalitiesInStudy),
+            tags::SOP_CLASSES_IN_STUDY => Some(Self::SopClassesInStudy),
+            _ => None,
+        }
+    }
+
+    fn source(self) -> (&'static str, &'static str) {
+        match self {
+            Self::ModalitiesInStudy => (SERIES.name, "modality"),
+            Self::SopClassesInStudy => (INSTANCES.name, "sop_class_uid"),
+        }
+    }
+
+    fn match_vr(self) -> VR {
+        match self {
+            Self::ModalitiesInStudy => VR::CS,
+            Self::SopClassesInStudy => VR::UI,
+        }
+    }
+
+    fn vr(self) -> &'static str {
+        match self {
+            Self::ModalitiesInStudy => "CS",
+            Self::SopClassesInStudy => "UI",
+        }
+    }
+
+    /// Distinct, sorted source values joined as one multi-valued string.
+    fn select_sql(self) -> String {
+        let (table, column) = self.source();
+        format!(
+            "(SELECT group_concat({column}, '\\') FROM (SELECT DISTINCT agg.{column} FROM {table} agg WHERE agg.study_instance_uid = {}.study_instance_uid AND agg.{column} <> '' ORDER BY agg.{column}))",
+            STUDIES.alias
+        )
+    }
+}
+
 fn instance_attributes_column() -> &'static str {
     "i.attributes"
 }
@@ -688,9 +876,11 @@ fn mapped_projection_body(vr: &str, value: &str) -> serde_json::Value {
             }],
         })
     } else {
+        // Mapped text VRs cannot contain a backslash, so it only ever
+        // separates values of a multi-valued projection.
         serde_json::json!({
             "vr": vr,
-            "Value": [value],
+            "Value": value.split('\\').collect::<Vec<_>>(),
         })
     }
 }
@@ -780,11 +970,13 @@ mod tests {
     use dicom_dictionary_std::tags;
     use rustcoon_index::{
         AttributePath, CatalogQuery, ItemSelector, MatchingRule, Paging,
-        PatientRootQueryRetrieveLevel, Predicate, QueryRetrieveScope, SequenceMatching,
-        SortDirection, SortKey, StudyRootQueryRetrieveLevel,
+        PatientRootQueryRetrieveLevel, Predicate, QueryRetrieveScope, RangeMatching,
+        SequenceMatching, SortDirection, SortKey, StudyRootQueryRetrieveLevel,
     };
 
-    use super::{compile_query, materialize_projection};
+    use super::{
+        BindValue, CompiledProjection, compile_query, like_pattern, materialize_projection,
+    };
     use crate::query::compile::ProjectionValue;
     use crate::schema::CatalogSchema;
 
@@ -826,8 +1018,10 @@ fn compiler_uses_indexed_columns_and_json_fallback_for_image_level() {
                 .sql
                 .contains("json_extract(i.attributes, '$.tag.\"00080070\".Value[0]')")
         );
-        assert!(compiled.sql.contains("ORDER BY o_0"));
-        assert_eq!(compiled.binds.len(), 4);
+        assert!(compiled.sql.contains("i.sop_instance_uid AS o_1"));
+        assert!(compiled.sql.contains("ORDER BY o_0 ASC, o_1 ASC"));
+        assert!(!compiled.sql.contains("ASC AS o_0"));
+        assert_eq!(compiled.binds.len(), 6);
     }
 
     #[test]
@@ -843,7 +1037,7 @@ fn compiler_supports_study_and_series_distinct_queries() {
         assert!(
             compiled
                 .sql
-                .contains("ROW_NUMBER() OVER (PARTITION BY s.study_instance_uid")
+                .contains("ROW_NUMBER() OVER (PARTITION BY d_0 ORDER BY o_0 ASC)")
         );
         assert!(compiled.sql.contains("ORDER BY o_0"));
 
@@ -856,7 +1050,7 @@ fn compiler_supports_study_and_series_distinct_queries() {
         assert!(
             compiled
                 .sql
-                .contains("ROW_NUMBER() OVER (PARTITION BY se.series_instance_uid")
+                .contains("ROW_NUMBER() OVER (PARTITION BY d_0 ORDER BY o_0 ASC)")
         );
         assert!(compiled.sql.contains("ORDER BY o_0"));
     }
@@ -882,9 +1076,38 @@ fn compiler_supports_patient_root_patient_queries() {
         assert!(
             compiled
                 .sql
-                .contains("ROW_NUMBER() OVER (PARTITION BY s.patient_id, s.patient_name")
+                .contains("ROW_NUMBER() OVER (PARTITION BY d_0, d_1")
+        );
+        assert!(
+            compiled
+                .sql
+                .contains("s.patient_name_search LIKE ? ESCAPE '\\'")
         );
-        assert!(compiled.sql.contains("CAST(s.patient_name AS TEXT) LIKE ?"));
+    }
+
+    #[test]
+    fn compiler_compares_text_columns_uncast_so_indexes_apply() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Series),
+            vec![AttributePath::from_tag(tags::SERIES_INSTANCE_UID)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::All(vec![
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::ACCESSION_NUMBER),
+                MatchingRule::SingleValue("ACC-1".to_string()),
+            ),
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::SERIES_NUMBER),
+                MatchingRule::SingleValue("7".to_string()),
+            ),
+        ]))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile series query");
+        assert!(compiled.sql.contains("s.accession_number = ?"));
+        assert!(compiled.sql.contains("CAST(se.series_number AS TEXT) = ?"));
     }
 
     #[test]
@@ -925,6 +1148,29 @@ fn compiler_supports_nested_concrete_paths_and_sequence_matching() {
         );
     }
 
+    #[test]
+    fn compiler_maps_utc_study_date_time_predicate_to_study_column() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::STUDY_INSTANCE_UID)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::StudyDateTimeUtc(RangeMatching::closed(
+            "20260101000000",
+            "20260131235959",
+        )))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile utc query");
+        assert!(
+            compiled
+                .sql
+                .contains("(s.study_date_time_utc >= ? AND s.study_date_time_utc <= ?)")
+        );
+        assert_eq!(compiled.binds.len(), 2);
+    }
+
     #[test]
     fn materialize_projection_builds_projected_object() {
         let projection = materialize_projection(&[
@@ -982,4 +1228,80 @@ fn materialize_projection_supports_mapped_person_name_vr() {
             "DOE^J1"
         );
     }
+
+    #[test]
+    fn compiler_binds_matching_values_instead_of_inlining_them() {
+        const HOSTILE: &str = "' OR '1'='1\\";
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::STUDY_INSTANCE_UID)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::All(vec![
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::PATIENT_ID),
+                MatchingRule::SingleValue(HOSTILE.to_string()),
+            ),
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::INSTITUTION_NAME),
+                MatchingRule::SingleValue(HOSTILE.to_string()),
+            ),
+        ]))
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile study query");
+        assert!(!compiled.sql.contains("OR '1'='1"));
+        let bound = compiled
+            .binds
+            .iter()
+            .filter(|bind| matches!(bind, BindValue::Text(value) if value == HOSTILE))
+            .count();
+        assert_eq!(bound, 2);
+    }
+
+    #[test]
+    fn wildcard_matching_escapes_literal_like_characters() {
+        assert_eq!(like_pattern("DOE*"), "DOE%");
+        assert_eq!(like_pattern("A?C"), "A_C");
+        assert_eq!(like_pattern("100%_A\\*"), "100\\%\\_A\\\\%");
+    }
+
+    #[test]
+    fn person_names_match_and_sort_on_folded_search_columns() {
+        let schema = CatalogSchema::new();
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::PATIENT_NAME)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::Attribute(
+            AttributePath::from_tag(tags::REFERRING_PHYSICIAN_NAME),
+            MatchingRule::Wildcard("müller*".to_string()),
+        ))
+        .unwrap()
+        .with_sort(vec![SortKey {
+            path: AttributePath::from_tag(tags::PATIENT_NAME),
+            direction: SortDirection::Ascending,
+        }])
+        .unwrap();
+
+        let compiled = compile_query(&schema, &query).expect("compile study query");
+        assert!(
+            compiled
+                .sql
+                .contains("s.referring_physician_name_search LIKE ? ESCAPE '\\'")
+        );
+        assert!(compiled.sql.contains("s.patient_name_search AS o_0"));
+        assert!(matches!(
+            compiled.binds.as_slice(),
+            [BindValue::Text(like), BindValue::Text(lower), BindValue::Text(upper)]
+                if like == "MULLER%" && lower == "MULLER" && upper == "MULLER\u{10FFFF}"
+        ));
+        assert!(matches!(
+            compiled.projections.as_slice(),
+            [CompiledProjection::Mapped { select_sql, .. }] if select_sql.starts_with("s.patient_name")
+                && !select_sql.contains("_search")
+        ));
+    }
 }
@@ -25,6 +25,8 @@ struct StudyRowData {
     patient_name: Option<String>,
     accession_number: Option<String>,
     study_id: Option<String>,
+    instance_count: i64,
+    size_bytes: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,7 +65,14 @@ async fn get_study(
     ) -> Result<Option<CatalogStudyEntry>, IndexError> {
         let row = sqlx::query(
             r#"
-            SELECT study_instance_uid, patient_id, patient_name, accession_number, study_id
+            SELECT
+                study_instance_uid,
+                patient_id,
+                patient_name,
+                accession_number,
+                study_id,
+                instance_count,
+                size_bytes
             FROM studies
             WHERE study_instance_uid = ?
             "#,
@@ -180,6 +189,31 @@ async fn query(&self, query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, In
 
         Ok(Page::new(items, compiled.paging, None))
     }
+
+    async fn list_superseded_blobs(
+        &self,
+        sop_instance_uid: &SopInstanceUid,
+    ) -> Result<Vec<StoredObjectRef>, IndexError> {
+        let rows: Vec<(String, Option<String>, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT blob_key, blob_version, blob_size_bytes
+            FROM superseded_blobs
+            WHERE sop_instance_uid = ?
+            ORDER BY superseded_at DESC, blob_key DESC
+            "#,
+        )
+        .bind(sop_instance_uid.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| map_sqlx(IndexOperation::ListSupersededBlobs, err))?;
+
+        rows.into_iter()
+            .filter_map(|(key, version, size_bytes)| {
+                blob_ref_from_parts(Some(key), version, size_bytes).transpose()
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|err| IndexError::backend("sqlite", IndexOperation::ListSupersededBlobs, err))
+    }
 }
 
 fn row_to_study_entry(row: sqlx::sqlite::SqliteRow) -> Result<CatalogStudyEntry, IndexError> {
@@ -199,6 +233,12 @@ fn row_to_study_entry(row: sqlx::sqlite::SqliteRow) -> Result<CatalogStudyEntry,
         study_id: row
             .try_get::<Option<String>, _>("study_id")
             .map_err(|err| IndexError::backend("sqlite", IndexOperation::GetStudy, err))?,
+        instance_count: row
+            .try_get::<i64, _>("instance_count")
+            .map_err(|err| IndexError::backend("sqlite", IndexOperation::GetStudy, err))?,
+        size_bytes: row
+            .try_get::<i64, _>("size_bytes")
+            .map_err(|err| IndexError::backend("sqlite", IndexOperation::GetStudy, err))?,
     })
 }
 
@@ -281,6 +321,8 @@ fn study_entry_from_data(data: StudyRowData) -> Result<CatalogStudyEntry, IndexE
             DicomPatient::new(data.patient_id, data.patient_name),
             DicomStudyMetadata::new(data.accession_number, data.study_id),
         ),
+        instance_count: data.instance_count.max(0) as u64,
+        size_bytes: data.size_bytes.max(0) as u64,
     })
 }
 
@@ -42,12 +42,24 @@ pub(crate) fn definitions() -> Vec<AttributeMapping> {
             column: "patient_id",
             vr: MappedVr::LongString,
         },
+        AttributeMapping {
+            tag: tags::ISSUER_OF_PATIENT_ID,
+            table: TableId::Study,
+            column: "issuer_of_patient_id",
+            vr: MappedVr::LongString,
+        },
         AttributeMapping {
             tag: tags::PATIENT_NAME,
             table: TableId::Study,
             column: "patient_name",
             vr: MappedVr::PersonName,
         },
+        AttributeMapping {
+            tag: tags::REFERRING_PHYSICIAN_NAME,
+            table: TableId::Study,
+            column: "referring_physician_name",
+            vr: MappedVr::PersonName,
+        },
         AttributeMapping {
             tag: tags::STUDY_INSTANCE_UID,
             table: TableId::Study,
@@ -84,6 +96,18 @@ pub(crate) fn definitions() -> Vec<AttributeMapping> {
             column: "series_number",
             vr: MappedVr::IntegerString,
         },
+        AttributeMapping {
+            tag: tags::BODY_PART_EXAMINED,
+            table: TableId::Series,
+            column: "body_part_examined",
+            vr: MappedVr::ShortString,
+        },
+        AttributeMapping {
+            tag: tags::LATERALITY,
+            table: TableId::Series,
+            column: "laterality",
+            vr: MappedVr::ShortString,
+        },
         AttributeMapping {
             tag: tags::SOP_INSTANCE_UID,
             table: TableId::Instance,
@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 
-pub(crate) use attributes::AttributeMapping;
+pub(crate) use attributes::{AttributeMapping, MappedVr};
 use dicom_core::Tag;
 use rustcoon_index::AttributePath;
 pub(crate) use tables::{INSTANCES, SERIES, STUDIES, TableId};
@@ -1,7 +1,13 @@
-use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+use rustcoon_dicom::fold_person_name;
+use rustcoon_index::study_date_time_utc;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Row, SqlitePool};
 
 use crate::config::SqliteCatalogConfig;
+use crate::query::deserialize_attributes;
 use crate::schema::CatalogSchema;
 
 #[derive(Debug, Clone)]
@@ -23,38 +29,266 @@ pub async fn connect(config: &SqliteCatalogConfig) -> Result<Self, sqlx::Error>
             .connection_string()
             .parse::<SqliteConnectOptions>()?
             .create_if_missing(true)
-            .foreign_keys(true);
+            .foreign_keys(true)
+            // Wildcard matching is case-sensitive, as in the Postgres catalog,
+            // and agrees with the prefix range the query compiler adds.
+            .pragma("case_sensitive_like", "ON");
         let pool = SqlitePoolOptions::new()
             .max_connections(config.max_connections())
             .connect_with(options)
             .await?;
 
-        run_migrations(&pool).await?;
+        if config.auto_migrate() {
+            run_migrations(&pool).await?;
+        } else {
+            verify_migrations(&pool).await?;
+        }
 
-        Ok(Self::new(pool))
+        let store = Self::new(pool);
+        store.run_backfills().await?;
+        Ok(store)
     }
 
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// Fills derived study columns for rows written before they existed.
+    pub async fn run_backfills(&self) -> Result<(), sqlx::Error> {
+        self.backfill_person_name_search().await?;
+        self.backfill_study_date_time_utc().await?;
+        Ok(())
+    }
+
+    /// Fills the UTC study timestamp for studies written before it existed,
+    /// from the latest instance document of each study. Runs in batches once;
+    /// returns the number of studies updated.
+    pub async fn backfill_study_date_time_utc(&self) -> Result<u64, sqlx::Error> {
+        const NAME: &str = "study_date_time_utc";
+        if backfill_completed(&self.pool, NAME).await? {
+            return Ok(0);
+        }
+
+        let mut updated = 0;
+        let mut after = String::new();
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    s.study_instance_uid,
+                    (
+                        SELECT i.attributes
+                        FROM instances i
+                        WHERE i.study_instance_uid = s.study_instance_uid
+                        ORDER BY i.updated_at DESC
+                        LIMIT 1
+                    ) AS attributes
+                FROM studies s
+                WHERE s.study_date_time_utc IS NULL AND s.study_instance_uid > ?
+                ORDER BY s.study_instance_uid
+                LIMIT ?
+                "#,
+            )
+            .bind(&after)
+            .bind(BACKFILL_BATCH_SIZE)
+            .fetch_all(&self.pool)
+            .await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after = last.try_get("study_instance_uid")?;
+
+            let mut tx = self.pool.begin().await?;
+            for row in &rows {
+                let Some(value) = row
+                    .try_get::<Option<serde_json::Value>, _>("attributes")?
+                    .and_then(|attributes| deserialize_attributes(attributes).ok())
+                    .and_then(|attributes| study_date_time_utc(&attributes))
+                else {
+                    continue;
+                };
+                sqlx::query(
+                    "UPDATE studies SET study_date_time_utc = ? WHERE study_instance_uid = ?",
+                )
+                .bind(value)
+                .bind(row.try_get::<String, _>("study_instance_uid")?)
+                .execute(&mut *tx)
+                .await?;
+                updated += 1;
+            }
+            tx.commit().await?;
+        }
+
+        mark_backfill_completed(&self.pool, NAME).await?;
+        Ok(updated)
+    }
+
+    /// Recomputes the folded person-name search columns for studies written
+    /// before they existed or under an earlier folding. Folding happens in
+    /// Rust so it matches write-time values exactly. Runs in batches once;
+    /// returns the number of studies updated.
+    pub async fn backfill_person_name_search(&self) -> Result<u64, sqlx::Error> {
+        const NAME: &str = "person_name_search";
+        if backfill_completed(&self.pool, NAME).await? {
+            return Ok(0);
+        }
+
+        let mut updated = 0;
+        let mut after = String::new();
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    study_instance_uid,
+                    patient_name,
+                    referring_physician_name,
+                    patient_name_search,
+                    referring_physician_name_search
+                FROM studies
+                WHERE (patient_name IS NOT NULL OR referring_physician_name IS NOT NULL)
+                  AND study_instance_uid > ?
+                ORDER BY study_instance_uid
+                LIMIT ?
+                "#,
+            )
+            .bind(&after)
+            .bind(BACKFILL_BATCH_SIZE)
+            .fetch_all(&self.pool)
+            .await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after = last.try_get("study_instance_uid")?;
+
+            let mut tx = self.pool.begin().await?;
+            for row in &rows {
+                let fold = |column| {
+                    row.try_get::<Option<String>, _>(column)
+                        .map(|name| name.as_deref().map(fold_person_name))
+                };
+                let patient_name_search = fold("patient_name")?;
+                let referring_physician_name_search = fold("referring_physician_name")?;
+                if patient_name_search == row.try_get("patient_name_search")?
+                    && referring_physician_name_search
+                        == row.try_get("referring_physician_name_search")?
+                {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    UPDATE studies
+                    SET patient_name_search = ?, referring_physician_name_search = ?
+                    WHERE study_instance_uid = ?
+                    "#,
+                )
+                .bind(patient_name_search)
+                .bind(referring_physician_name_search)
+                .bind(row.try_get::<String, _>("study_instance_uid")?)
+                .execute(&mut *tx)
+                .await?;
+                updated += 1;
+            }
+            tx.commit().await?;
+        }
+
+        mark_backfill_completed(&self.pool, NAME).await?;
+        Ok(updated)
+    }
 }
 
-async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    let migrator = sqlx::migrate::Migrator::new(std::path::Path::new(concat!(
+const BACKFILL_BATCH_SIZE: i64 = 500;
+
+async fn backfill_completed(pool: &SqlitePool, name: &str) -> Result<bool, sqlx::Error> {
+    Ok(
+        sqlx::query("SELECT 1 FROM catalog_backfills WHERE name = ?")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?
+            .is_some(),
+    )
+}
+
+async fn mark_backfill_completed(pool: &SqlitePool, name: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO catalog_backfills (name) VALUES (?) ON CONFLICT (name) DO NOTHING")
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+async fn migrator() -> Result<Migrator, sqlx::Error> {
+    Ok(Migrator::new(std::path::Path::new(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/../../migrations/sqlite"
     )))
-    .await?;
-    migrator.run(pool).await?;
+    .await?)
+}
+
+async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    migrator().await?.run(pool).await?;
     Ok(())
 }
 
+/// Checks the schema without changing it, failing on migrations that are
+/// pending or whose applied checksum no longer matches the bundled file.
+async fn verify_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let migrator = migrator().await?;
+    let tracked = sqlx::query(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+    )
+    .fetch_optional(pool)
+    .await?
+    .is_some();
+    let applied = if tracked {
+        pool.acquire().await?.list_applied_migrations().await?
+    } else {
+        Vec::new()
+    };
+    let applied = applied
+        .into_iter()
+        .map(|migration| (migration.version, migration.checksum))
+        .collect::<HashMap<_, _>>();
+
+    let mut pending = Vec::new();
+    for migration in migrator
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+    {
+        match applied.get(&migration.version) {
+            Some(checksum) if *checksum != migration.checksum => {
+                return Err(MigrateError::VersionMismatch(migration.version).into());
+            }
+            Some(_) => {}
+            None => pending.push(format!("{} ({})", migration.version, migration.description)),
+        }
+    }
+
+    if pending.is_empty() {
+        return Ok(());
+    }
+    Err(sqlx::Error::Configuration(
+        format!(
+            "catalog schema has pending migrations: {}; apply them or enable auto_migrate",
+            pending.join(", ")
+        )
+        .into(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
+    use dicom_dictionary_std::tags;
+    use rustcoon_index::{
+        AttributePath, CatalogQuery, MatchingRule, Predicate, QueryRetrieveScope,
+        StudyRootQueryRetrieveLevel,
+    };
+    use sqlx::Row;
     use sqlx::sqlite::SqlitePoolOptions;
 
     use crate::config::SqliteCatalogConfig;
-    use crate::store::SqliteCatalogStore;
+    use crate::query::{BindValue, compile_query};
+    use crate::schema::CatalogSchema;
+    use crate::store::{SqliteCatalogStore, run_migrations, verify_migrations};
 
     #[tokio::test]
     async fn new_initializes_store_with_pool() {
@@ -85,4 +319,181 @@ async fn connect_applies_schema_migrations() {
 
         assert!(row.is_some());
     }
+
+    #[tokio::test]
+    async fn backfill_refolds_stale_and_missing_person_name_search_values() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        sqlx::query(
+            r#"
+            DELETE FROM catalog_backfills;
+            INSERT INTO studies (
+                study_instance_uid, patient_name, referring_physician_name,
+                patient_name_search, referring_physician_name_search
+            )
+            VALUES
+                ('1.2.3', 'Müller^Hans', 'Ødegård^Ola', NULL, 'ØDEGARD^OLA'),
+                ('1.2.4', NULL, NULL, NULL, NULL),
+                ('1.2.5', 'Doe^Jane', NULL, 'DOE^JANE', NULL)
+            "#,
+        )
+        .execute(store.pool())
+        .await
+        .expect("insert legacy studies");
+
+        assert_eq!(
+            store.backfill_person_name_search().await.expect("backfill"),
+            1
+        );
+        assert_eq!(store.backfill_person_name_search().await.expect("rerun"), 0);
+
+        let row = sqlx::query(
+            "SELECT patient_name_search, referring_physician_name_search FROM studies WHERE study_instance_uid = '1.2.3'",
+        )
+        .fetch_one(store.pool())
+        .await
+        .expect("backfilled study");
+        assert_eq!(
+            row.get::<Option<String>, _>("patient_name_search")
+                .as_deref(),
+            Some("MULLER^HANS")
+        );
+        assert_eq!(
+            row.get::<Option<String>, _>("referring_physician_name_search")
+                .as_deref(),
+            Some("ODEGARD^OLA")
+        );
+    }
+
+    #[tokio::test]
+    async fn backfill_derives_utc_study_timestamps_once() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        sqlx::query(
+            r#"
+            DELETE FROM catalog_backfills;
+            INSERT INTO studies (study_instance_uid) VALUES ('1.2.3'), ('1.2.4');
+            INSERT INTO series (series_instance_uid, study_instance_uid) VALUES ('1.2.3.1', '1.2.3');
+            INSERT INTO instances (
+                sop_instance_uid, study_instance_uid, series_instance_uid, sop_class_uid, attributes
+            ) VALUES (
+                '1.2.3.1.1', '1.2.3', '1.2.3.1', '1.2.840.10008.5.1.4.1.1.2',
+                '{"tag":{"00080020":{"vr":"DA","Value":["20260411"]},"00080030":{"vr":"TM","Value":["233000"]},"00080201":{"vr":"SH","Value":["-0500"]}}}'
+            );
+            "#,
+        )
+        .execute(store.pool())
+        .await
+        .expect("insert legacy study");
+
+        assert_eq!(
+            store
+                .backfill_study_date_time_utc()
+                .await
+                .expect("backfill"),
+            1
+        );
+        sqlx::query("UPDATE studies SET study_date_time_utc = NULL")
+            .execute(store.pool())
+            .await
+            .expect("clear");
+        assert_eq!(
+            store.backfill_study_date_time_utc().await.expect("rerun"),
+            0
+        );
+
+        sqlx::query("DELETE FROM catalog_backfills")
+            .execute(store.pool())
+            .await
+            .expect("reset marker");
+        store
+            .backfill_study_date_time_utc()
+            .await
+            .expect("backfill");
+        let value: Option<String> = sqlx::query_scalar(
+            "SELECT study_date_time_utc FROM studies WHERE study_instance_uid = '1.2.3'",
+        )
+        .fetch_one(store.pool())
+        .await
+        .expect("backfilled study");
+        assert_eq!(value.as_deref(), Some("20260412043000"));
+    }
+
+    #[tokio::test]
+    async fn connect_without_auto_migrate_reports_pending_migrations() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_auto_migrate(false);
+        let error = SqliteCatalogStore::connect(&config)
+            .await
+            .expect_err("pending migrations");
+
+        let message = error.to_string();
+        assert!(message.contains("pending migrations"));
+        assert!(message.contains("20260412120000 (index schema)"));
+    }
+
+    #[tokio::test]
+    async fn verify_migrations_detects_drifted_checksums() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("pool");
+        run_migrations(&pool).await.expect("migrate");
+        verify_migrations(&pool).await.expect("schema up to date");
+
+        sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 20260412120000")
+            .execute(&pool)
+            .await
+            .expect("tamper checksum");
+
+        assert!(matches!(
+            verify_migrations(&pool).await,
+            Err(sqlx::Error::Migrate(error))
+                if matches!(*error, sqlx::migrate::MigrateError::VersionMismatch(20260412120000))
+        ));
+    }
+
+    #[tokio::test]
+    async fn accession_number_wildcards_use_the_index_case_sensitively() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        let query = CatalogQuery::new(
+            QueryRetrieveScope::StudyRoot(StudyRootQueryRetrieveLevel::Study),
+            vec![AttributePath::from_tag(tags::STUDY_INSTANCE_UID)],
+        )
+        .unwrap()
+        .with_predicate(Predicate::Attribute(
+            AttributePath::from_tag(tags::ACCESSION_NUMBER),
+            MatchingRule::Wildcard("ACC*".to_string()),
+        ))
+        .unwrap();
+        let compiled = compile_query(&CatalogSchema::new(), &query).expect("compile query");
+        let explain_sql = format!("EXPLAIN QUERY PLAN {}", compiled.sql);
+        let mut explain = sqlx::query(&explain_sql);
+        for bind in &compiled.binds {
+            explain = match bind {
+                BindValue::Text(value) => explain.bind(value),
+                BindValue::Int8(value) => explain.bind(*value),
+            };
+        }
+        let plan = explain
+            .fetch_all(store.pool())
+            .await
+            .expect("explain query")
+            .iter()
+            .map(|row| row.get::<String, _>("detail"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(plan.contains("idx_studies_accession_number"), "{plan}");
+
+        let matches = async |pattern: &str| {
+            sqlx::query_scalar::<_, bool>("SELECT 'ACC-001' LIKE ?")
+                .bind(pattern)
+                .fetch_one(store.pool())
+                .await
+                .expect("match")
+        };
+        assert!(matches("ACC%").await);
+        assert!(!matches("acc%").await);
+    }
 }
@@ -1,15 +1,26 @@
 use async_trait::async_trait;
 use dicom_dictionary_std::tags;
+use rustcoon_dicom::{DicomInstanceIdentity, fold_person_name};
 use rustcoon_index::{
     CatalogUpsertOutcome, CatalogWriteStore, IndexError, IndexOperation, InstanceUpsertRequest,
-    StoredObjectRef,
+    StoredObjectRef, StudyQuota, check_hierarchy_unchanged, promoted_text, study_date_time_utc,
+    without_receipt,
 };
 use sqlx::Row;
 
 use crate::error::map_sqlx;
-use crate::query::serialize_attributes;
+use crate::query::{deserialize_attributes, serialize_attributes};
 use crate::store::SqliteCatalogStore;
 
+const ADJUST_STUDY_COUNTERS_SQL: &str = r#"
+    UPDATE studies
+    SET
+        instance_count = instance_count + ?2,
+        size_bytes = size_bytes + ?3
+    WHERE study_instance_uid = ?1
+    RETURNING instance_count, size_bytes
+"#;
+
 #[derive(Debug, Clone, PartialEq)]
 struct DesiredInstanceState {
     sop_class_uid: String,
@@ -51,16 +62,12 @@ async fn upsert_instance(
         let study = request.record.study();
         let series = request.record.series();
         let instance = request.record.instance();
-        let attributes = serialize_attributes(&request.attributes).map_err(
-            |err: Box<dyn std::error::Error + Send + Sync>| {
-                IndexError::backend(
-                    "sqlite",
-                    IndexOperation::UpsertInstance,
-                    std::io::Error::other(err.to_string()),
-                )
-            },
-        )?;
+        let attributes = serialize_document(&request.attributes, IndexOperation::UpsertInstance)?;
+
+        ensure_hierarchy_unchanged(&mut tx, identity, IndexOperation::UpsertInstance).await?;
 
+        let referring_physician_name =
+            promoted_text(&request.attributes, tags::REFERRING_PHYSICIAN_NAME);
         sqlx::query(
             r#"
             INSERT INTO studies (
@@ -68,14 +75,24 @@ async fn upsert_instance(
                 patient_id,
                 patient_name,
                 accession_number,
-                study_id
+                study_id,
+                study_date_time_utc,
+                issuer_of_patient_id,
+                referring_physician_name,
+                patient_name_search,
+                referring_physician_name_search
             )
-            VALUES (?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT (study_instance_uid) DO UPDATE SET
                 patient_id = excluded.patient_id,
                 patient_name = excluded.patient_name,
                 accession_number = excluded.accession_number,
                 study_id = excluded.study_id,
+                study_date_time_utc = excluded.study_date_time_utc,
+                issuer_of_patient_id = excluded.issuer_of_patient_id,
+                referring_physician_name = excluded.referring_physician_name,
+                patient_name_search = excluded.patient_name_search,
+                referring_physician_name_search = excluded.referring_physician_name_search,
                 updated_at = CURRENT_TIMESTAMP
             "#,
         )
@@ -84,6 +101,14 @@ async fn upsert_instance(
         .bind(patient.patient_name())
         .bind(study.accession_number())
         .bind(study.study_id())
+        .bind(study_date_time_utc(&request.attributes))
+        .bind(promoted_text(
+            &request.attributes,
+            tags::ISSUER_OF_PATIENT_ID,
+        ))
+        .bind(&referring_physician_name)
+        .bind(patient.patient_name().map(fold_person_name))
+        .bind(referring_physician_name.as_deref().map(fold_person_name))
         .execute(&mut *tx)
         .await
         .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
@@ -94,13 +119,17 @@ async fn upsert_instance(
                 series_instance_uid,
                 study_instance_uid,
                 modality,
-                series_number
+                series_number,
+                body_part_examined,
+                laterality
             )
-            VALUES (?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?)
             ON CONFLICT (series_instance_uid) DO UPDATE SET
                 study_instance_uid = excluded.study_instance_uid,
                 modality = excluded.modality,
                 series_number = excluded.series_number,
+                body_part_examined = excluded.body_part_examined,
+                laterality = excluded.laterality,
                 updated_at = CURRENT_TIMESTAMP
             "#,
         )
@@ -108,51 +137,32 @@ async fn upsert_instance(
         .bind(identity.study_instance_uid().as_str())
         .bind(series.modality())
         .bind(series.series_number().map(|value| value as i32))
+        .bind(promoted_text(&request.attributes, tags::BODY_PART_EXAMINED))
+        .bind(promoted_text(&request.attributes, tags::LATERALITY))
         .execute(&mut *tx)
         .await
         .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
 
-        let existing = sqlx::query(
-            r#"
-            SELECT
-                sop_class_uid,
-                instance_number,
-                acquisition_date_time,
-                transfer_syntax_uid,
-                attributes,
-                blob_key,
-                blob_version,
-                blob_size_bytes
-            FROM instances
-            WHERE sop_instance_uid = ?
-            "#,
-        )
-        .bind(identity.sop_instance_uid().as_str())
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
+        let existing =
+            select_existing_instance(&mut tx, identity, IndexOperation::UpsertInstance).await?;
 
-        let blob_key = request.blob.as_ref().map(|blob| blob.key.to_string());
-        let blob_version = request.blob.as_ref().and_then(|blob| blob.version.clone());
-        let blob_size = request
-            .blob
-            .as_ref()
-            .and_then(|blob| blob.size_bytes)
-            .map(|value| value as i64);
+        let (blob_key, blob_version, blob_size) = blob_columns(request.blob.as_ref());
         let desired_state = DesiredInstanceState::from_request(
             &request,
-            attributes.clone(),
+            comparable_attributes(attributes.clone()),
             blob_key.clone(),
             blob_version.clone(),
             blob_size,
         );
 
-        let outcome = if let Some(row) = existing {
-            let unchanged = ExistingInstanceState::try_from_row(&row)
-                .map(|existing| existing.matches(&desired_state))
-                .unwrap_or(false);
+        let previous_blob = existing
+            .as_ref()
+            .map(PreviousBlob::try_from_row)
+            .transpose()
+            .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
 
-            if unchanged {
+        let outcome = if let Some(row) = existing {
+            if ExistingInstanceState::matches_row(&row, &desired_state) {
                 CatalogUpsertOutcome::Unchanged
             } else {
                 sqlx::query(
@@ -227,6 +237,32 @@ async fn upsert_instance(
             CatalogUpsertOutcome::Created
         };
 
+        if outcome != CatalogUpsertOutcome::Unchanged {
+            let (instance_delta, released_bytes) = match &previous_blob {
+                Some(previous_blob) => (
+                    0,
+                    replace_blob(
+                        &mut tx,
+                        identity.sop_instance_uid().as_str(),
+                        identity.study_instance_uid().as_str(),
+                        previous_blob,
+                        desired_state.blob_key.as_deref(),
+                        IndexOperation::UpsertInstance,
+                    )
+                    .await?,
+                ),
+                None => (1, 0),
+            };
+            adjust_study_counters(
+                &mut tx,
+                identity.study_instance_uid().as_str(),
+                (instance_delta, blob_size.unwrap_or(0) - released_bytes),
+                request.study_quota.as_ref(),
+                IndexOperation::UpsertInstance,
+            )
+            .await?;
+        }
+
         tx.commit()
             .await
             .map_err(|err| map_sqlx(IndexOperation::UpsertInstance, err))?;
@@ -234,12 +270,76 @@ async fn upsert_instance(
         Ok(outcome)
     }
 
+    async fn preview_upsert(
+        &self,
+        request: &InstanceUpsertRequest,
+    ) -> Result<CatalogUpsertOutcome, IndexError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| map_sqlx(IndexOperation::PreviewUpsert, err))?;
+        let identity = request.record.identity();
+        ensure_hierarchy_unchanged(&mut tx, identity, IndexOperation::PreviewUpsert).await?;
+        let existing =
+            select_existing_instance(&mut tx, identity, IndexOperation::PreviewUpsert).await?;
+        let Some(row) = existing else {
+            return Ok(CatalogUpsertOutcome::Created);
+        };
+
+        let attributes = serialize_document(&request.attributes, IndexOperation::PreviewUpsert)?;
+        let (blob_key, blob_version, blob_size) = blob_columns(request.blob.as_ref());
+        let desired_state = DesiredInstanceState::from_request(
+            request,
+            comparable_attributes(attributes),
+            blob_key,
+            blob_version,
+            blob_size,
+        );
+        Ok(
+            if ExistingInstanceState::matches_row(&row, &desired_state) {
+                CatalogUpsertOutcome::Unchanged
+            } else {
+                CatalogUpsertOutcome::Updated
+            },
+        )
+    }
+
     async fn attach_blob(
         &self,
         identity: &rustcoon_dicom::DicomInstanceIdentity,
         blob: StoredObjectRef,
     ) -> Result<(), IndexError> {
-        let result = sqlx::query(
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
+
+        let existing = sqlx::query(
+            r#"
+            SELECT study_instance_uid, blob_key, blob_version, blob_size_bytes
+            FROM instances
+            WHERE sop_instance_uid = ?
+            "#,
+        )
+        .bind(identity.sop_instance_uid().as_str())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
+        let Some(row) = existing else {
+            return Err(IndexError::instance_not_found(
+                identity.sop_instance_uid().clone(),
+            ));
+        };
+        let (study_instance_uid, previous_blob) = row
+            .try_get::<String, _>("study_instance_uid")
+            .and_then(|study| Ok((study, PreviousBlob::try_from_row(&row)?)))
+            .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
+        let blob_key = blob.key.to_string();
+
+        let size_bytes = blob.size_bytes.map(|value| value as i64);
+        sqlx::query(
             r#"
             UPDATE instances
             SET
@@ -251,21 +351,224 @@ async fn attach_blob(
             "#,
         )
         .bind(identity.sop_instance_uid().as_str())
-        .bind(blob.key.to_string())
+        .bind(&blob_key)
         .bind(blob.version)
-        .bind(blob.size_bytes.map(|value| value as i64))
-        .execute(&self.pool)
+        .bind(size_bytes)
+        .execute(&mut *tx)
         .await
         .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))?;
 
-        if result.rows_affected() == 0 {
-            return Err(IndexError::instance_not_found(
-                identity.sop_instance_uid().clone(),
-            ));
+        let released_bytes = replace_blob(
+            &mut tx,
+            identity.sop_instance_uid().as_str(),
+            &study_instance_uid,
+            &previous_blob,
+            Some(&blob_key),
+            IndexOperation::AttachBlob,
+        )
+        .await?;
+        adjust_study_counters(
+            &mut tx,
+            &study_instance_uid,
+            (0, size_bytes.unwrap_or(0) - released_bytes),
+            None,
+            IndexOperation::AttachBlob,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|err| map_sqlx(IndexOperation::AttachBlob, err))
+    }
+}
+
+/// Blob an existing instance referenced before this write.
+#[derive(Debug, Clone, PartialEq)]
+struct PreviousBlob {
+    key: Option<String>,
+    version: Option<String>,
+    size_bytes: Option<i64>,
+}
+
+impl PreviousBlob {
+    fn try_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            key: row.try_get::<Option<String>, _>("blob_key")?,
+            version: row.try_get::<Option<String>, _>("blob_version")?,
+            size_bytes: row.try_get::<Option<i64>, _>("blob_size_bytes")?,
+        })
+    }
+}
+
+/// Tracks an instance moving from `previous` to the blob at `next_key`. A
+/// previous blob under another key is kept in `superseded_blobs` rather than
+/// orphaned, and a superseded blob that becomes current again leaves it.
+/// Returns the bytes no longer counted against the study.
+async fn replace_blob(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    sop_instance_uid: &str,
+    study_instance_uid: &str,
+    previous: &PreviousBlob,
+    next_key: Option<&str>,
+    operation: IndexOperation,
+) -> Result<i64, IndexError> {
+    let reactivated_bytes = match next_key {
+        Some(next_key) if previous.key.as_deref() != Some(next_key) => {
+            let reactivated: Option<Option<i64>> = sqlx::query_scalar(
+                "DELETE FROM superseded_blobs WHERE blob_key = ? RETURNING blob_size_bytes",
+            )
+            .bind(next_key)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|err| map_sqlx(operation, err))?;
+            reactivated.flatten().unwrap_or(0)
         }
+        _ => 0,
+    };
 
-        Ok(())
+    let Some(previous_key) = previous.key.as_deref() else {
+        return Ok(reactivated_bytes);
+    };
+    if next_key == Some(previous_key) {
+        return Ok(reactivated_bytes + previous.size_bytes.unwrap_or(0));
     }
+
+    sqlx::query(
+        r#"
+        INSERT INTO superseded_blobs (
+            blob_key,
+            sop_instance_uid,
+            study_instance_uid,
+            blob_version,
+            blob_size_bytes
+        )
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (blob_key) DO NOTHING
+        "#,
+    )
+    .bind(previous_key)
+    .bind(sop_instance_uid)
+    .bind(study_instance_uid)
+    .bind(&previous.version)
+    .bind(previous.size_bytes)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| map_sqlx(operation, err))?;
+
+    Ok(reactivated_bytes)
+}
+
+/// Applies instance-count and size deltas to a study's maintained totals and
+/// checks the updated totals against `quota`.
+async fn adjust_study_counters(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    study_instance_uid: &str,
+    (instance_delta, size_delta): (i64, i64),
+    quota: Option<&StudyQuota>,
+    operation: IndexOperation,
+) -> Result<(), IndexError> {
+    if instance_delta == 0 && size_delta == 0 {
+        return Ok(());
+    }
+
+    let totals: Option<(i64, i64)> = sqlx::query_as(ADJUST_STUDY_COUNTERS_SQL)
+        .bind(study_instance_uid)
+        .bind(instance_delta)
+        .bind(size_delta)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|err| map_sqlx(operation, err))?;
+
+    match (quota, totals) {
+        (Some(quota), Some((instance_count, size_bytes))) => quota.check(
+            study_instance_uid,
+            (instance_count.max(0) as u64, size_bytes.max(0) as u64),
+            (instance_delta, size_delta),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Loads the recorded parents of the incoming series and instance; the
+/// upserts below would otherwise re-parent them silently.
+async fn ensure_hierarchy_unchanged(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    identity: &DicomInstanceIdentity,
+    operation: IndexOperation,
+) -> Result<(), IndexError> {
+    let series_study: Option<String> =
+        sqlx::query_scalar("SELECT study_instance_uid FROM series WHERE series_instance_uid = ?")
+            .bind(identity.series_instance_uid().as_str())
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|err| map_sqlx(operation, err))?;
+    let instance_parents: Option<(String, String)> = sqlx::query_as(
+        "SELECT study_instance_uid, series_instance_uid FROM instances WHERE sop_instance_uid = ?",
+    )
+    .bind(identity.sop_instance_uid().as_str())
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|err| map_sqlx(operation, err))?;
+
+    check_hierarchy_unchanged(
+        identity,
+        series_study.as_deref(),
+        instance_parents
+            .as_ref()
+            .map(|(study, series)| (study.as_str(), series.as_str())),
+    )
+}
+
+async fn select_existing_instance(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    identity: &DicomInstanceIdentity,
+    operation: IndexOperation,
+) -> Result<Option<sqlx::sqlite::SqliteRow>, IndexError> {
+    sqlx::query(
+        r#"
+        SELECT
+            sop_class_uid,
+            instance_number,
+            acquisition_date_time,
+            transfer_syntax_uid,
+            attributes,
+            blob_key,
+            blob_version,
+            blob_size_bytes
+        FROM instances
+        WHERE sop_instance_uid = ?
+        "#,
+    )
+    .bind(identity.sop_instance_uid().as_str())
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|err| map_sqlx(operation, err))
+}
+
+fn serialize_document(
+    attributes: &rustcoon_index::DicomAttributeDocument,
+    operation: IndexOperation,
+) -> Result<serde_json::Value, IndexError> {
+    serialize_attributes(attributes).map_err(|err| {
+        IndexError::backend("sqlite", operation, std::io::Error::other(err.to_string()))
+    })
+}
+
+fn blob_columns(blob: Option<&StoredObjectRef>) -> (Option<String>, Option<String>, Option<i64>) {
+    (
+        blob.map(|blob| blob.key.to_string()),
+        blob.and_then(|blob| blob.version.clone()),
+        blob.and_then(|blob| blob.size_bytes)
+            .map(|value| value as i64),
+    )
+}
+
+/// Stored document as compared across deliveries: the receipt block records
+/// the delivery itself, so it never makes a re-store count as a change.
+fn comparable_attributes(attributes: serde_json::Value) -> serde_json::Value {
+    deserialize_attributes(attributes.clone())
+        .and_then(|document| serialize_attributes(&without_receipt(&document)))
+        .unwrap_or(attributes)
 }
 
 impl DesiredInstanceState {
@@ -315,13 +618,17 @@ fn try_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
             instance_number: row.try_get::<Option<i32>, _>("instance_number")?,
             acquisition_date_time: row.try_get::<Option<String>, _>("acquisition_date_time")?,
             transfer_syntax_uid: row.try_get::<Option<String>, _>("transfer_syntax_uid")?,
-            attributes: row.try_get::<serde_json::Value, _>("attributes")?,
+            attributes: comparable_attributes(row.try_get::<serde_json::Value, _>("attributes")?),
             blob_key: row.try_get::<Option<String>, _>("blob_key")?,
             blob_version: row.try_get::<Option<String>, _>("blob_version")?,
             blob_size_bytes: row.try_get::<Option<i64>, _>("blob_size_bytes")?,
         })
     }
 
+    fn matches_row(row: &sqlx::sqlite::SqliteRow, desired: &DesiredInstanceState) -> bool {
+        Self::try_from_row(row).is_ok_and(|existing| existing.matches(desired))
+    }
+
     fn matches(&self, desired: &DesiredInstanceState) -> bool {
         self.sop_class_uid == desired.sop_class_uid
             && self.instance_number == desired.instance_number
@@ -336,7 +643,7 @@ fn matches(&self, desired: &DesiredInstanceState) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use dicom_core::{DataElement, PrimitiveValue, VR};
+    use dicom_core::{DataElement, PrimitiveValue, Tag, VR};
     use dicom_dictionary_std::tags;
     use dicom_object::InMemDicomObject;
     use rustcoon_dicom::{
@@ -344,11 +651,16 @@ mod tests {
         DicomSeriesMetadata, DicomStudyMetadata, SeriesInstanceUid, SopClassUid, SopInstanceUid,
         StudyInstanceUid, TransferSyntaxUid,
     };
-    use rustcoon_index::{InstanceUpsertRequest, StoredObjectRef};
+    use rustcoon_index::{
+        CatalogReadStore, CatalogUpsertOutcome, CatalogWriteStore, IndexError,
+        InstanceUpsertRequest, StoredObjectRef, StudyQuota,
+    };
     use rustcoon_storage::BlobKey;
 
     use super::{DesiredInstanceState, ExistingInstanceState};
+    use crate::config::SqliteCatalogConfig;
     use crate::query::serialize_attributes;
+    use crate::store::SqliteCatalogStore;
 
     fn sample_request() -> InstanceUpsertRequest {
         let record = DicomInstanceRecord::new(
@@ -442,4 +754,271 @@ fn existing_state_match_detects_unchanged_and_changed_state() {
         };
         assert!(!changed.matches(&desired));
     }
+
+    #[tokio::test]
+    async fn preview_upsert_reports_the_outcome_without_writing() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        let request = sample_request();
+        let instance_count = async || -> i64 {
+            sqlx::query_scalar("SELECT COUNT(*) FROM instances")
+                .fetch_one(store.pool())
+                .await
+                .expect("count")
+        };
+
+        assert_eq!(
+            store.preview_upsert(&request).await.expect("preview new"),
+            CatalogUpsertOutcome::Created
+        );
+        assert_eq!(instance_count().await, 0);
+
+        store
+            .upsert_instance(request.clone())
+            .await
+            .expect("create");
+        assert_eq!(
+            store.preview_upsert(&request).await.expect("preview same"),
+            CatalogUpsertOutcome::Unchanged
+        );
+        let resized = request.clone().with_blob(
+            StoredObjectRef::new(BlobKey::new("instances/1.dcm").unwrap())
+                .with_version("etag-1")
+                .with_size_bytes(1024),
+        );
+        assert_eq!(
+            store
+                .preview_upsert(&resized)
+                .await
+                .expect("preview resized"),
+            CatalogUpsertOutcome::Updated
+        );
+        assert_eq!(
+            store
+                .get_study(request.record.identity().study_instance_uid())
+                .await
+                .expect("get study")
+                .expect("study")
+                .size_bytes,
+            512
+        );
+    }
+
+    #[tokio::test]
+    async fn redelivery_with_a_new_receipt_is_unchanged() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        let delivered_at = |received: &str| {
+            let mut request = sample_request();
+            let mut attributes = request.attributes.clone();
+            attributes.put(DataElement::new(
+                Tag(0x0009, 0x00F0),
+                VR::LO,
+                PrimitiveValue::from("RUSTCOON RECEIPT"),
+            ));
+            attributes.put(DataElement::new(
+                Tag(0x0009, 0xF002),
+                VR::DT,
+                PrimitiveValue::from(received),
+            ));
+            request.attributes = attributes;
+            request
+        };
+
+        assert_eq!(
+            store
+                .upsert_instance(delivered_at("20260411101530.000000+0000"))
+                .await
+                .expect("create"),
+            CatalogUpsertOutcome::Created
+        );
+        assert_eq!(
+            store
+                .upsert_instance(delivered_at("20260412080000.000000+0000"))
+                .await
+                .expect("redeliver"),
+            CatalogUpsertOutcome::Unchanged
+        );
+    }
+
+    #[tokio::test]
+    async fn study_counters_follow_created_updated_and_attached_blobs() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        let request = sample_request();
+        let identity = request.record.identity().clone();
+        let totals = async || {
+            let study = store
+                .get_study(identity.study_instance_uid())
+                .await
+                .expect("get study")
+                .expect("study");
+            (study.instance_count, study.size_bytes)
+        };
+
+        assert_eq!(
+            store
+                .upsert_instance(request.clone())
+                .await
+                .expect("create"),
+            CatalogUpsertOutcome::Created
+        );
+        assert_eq!(totals().await, (1, 512));
+
+        assert_eq!(
+            store
+                .upsert_instance(request.clone())
+                .await
+                .expect("repeat"),
+            CatalogUpsertOutcome::Unchanged
+        );
+        assert_eq!(totals().await, (1, 512));
+
+        let resized = request.with_blob(
+            StoredObjectRef::new(BlobKey::new("instances/1.dcm").unwrap())
+                .with_version("etag-2")
+                .with_size_bytes(2_048),
+        );
+        assert_eq!(
+            store.upsert_instance(resized).await.expect("update"),
+            CatalogUpsertOutcome::Updated
+        );
+        assert_eq!(totals().await, (1, 2_048));
+
+        store
+            .attach_blob(
+                &identity,
+                StoredObjectRef::new(BlobKey::new("instances/1.dcm").unwrap())
+                    .with_size_bytes(1_024),
+            )
+            .await
+            .expect("attach blob");
+        assert_eq!(totals().await, (1, 1_024));
+    }
+
+    #[tokio::test]
+    async fn restores_under_new_keys_keep_superseded_blobs_counted() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        let request = sample_request();
+        let identity = request.record.identity().clone();
+        let blob = |key: &str, size_bytes| {
+            StoredObjectRef::new(BlobKey::new(key).unwrap()).with_size_bytes(size_bytes)
+        };
+        let study_size = async || {
+            store
+                .get_study(identity.study_instance_uid())
+                .await
+                .expect("get study")
+                .expect("study")
+                .size_bytes
+        };
+        let superseded = async || {
+            store
+                .list_superseded_blobs(identity.sop_instance_uid())
+                .await
+                .expect("list superseded blobs")
+                .into_iter()
+                .map(|blob| (blob.key.to_string(), blob.size_bytes))
+                .collect::<Vec<_>>()
+        };
+
+        store
+            .upsert_instance(request.clone())
+            .await
+            .expect("create");
+        assert_eq!(
+            store
+                .upsert_instance(request.with_blob(blob("instances/1.v2.dcm", 1_000)))
+                .await
+                .expect("re-store"),
+            CatalogUpsertOutcome::Updated
+        );
+        assert_eq!(study_size().await, 1_512);
+        assert_eq!(
+            superseded().await,
+            [("instances/1.dcm".to_string(), Some(512))]
+        );
+
+        store
+            .attach_blob(&identity, blob("instances/1.dcm", 512))
+            .await
+            .expect("restore earlier blob");
+        assert_eq!(study_size().await, 1_512);
+        assert_eq!(
+            superseded().await,
+            [("instances/1.v2.dcm".to_string(), Some(1_000))]
+        );
+    }
+
+    #[tokio::test]
+    async fn upsert_enforces_the_study_quota_in_its_transaction() {
+        let config = SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1);
+        let store = SqliteCatalogStore::connect(&config).await.expect("connect");
+        let quota = StudyQuota {
+            max_instances: Some(1),
+            max_size_bytes: None,
+        };
+        let request = sample_request().with_study_quota(quota);
+        let mut second = sample_request().with_study_quota(quota);
+        second.record = DicomInstanceRecord::new(
+            DicomInstanceIdentity::new(
+                StudyInstanceUid::new("1.2.3").unwrap(),
+                SeriesInstanceUid::new("1.2.3.1").unwrap(),
+                SopInstanceUid::new("1.2.3.1.2").unwrap(),
+                SopClassUid::new("1.2.840.10008.5.1.4.1.1.2").unwrap(),
+            ),
+            request.record.patient().clone(),
+            request.record.study().clone(),
+            request.record.series().clone(),
+            request.record.instance().clone(),
+        );
+        second.blob = Some(
+            StoredObjectRef::new(BlobKey::new("instances/2.dcm").unwrap()).with_size_bytes(256),
+        );
+
+        store
+            .upsert_instance(request.clone())
+            .await
+            .expect("first instance");
+        let error = store
+            .upsert_instance(second.clone())
+            .await
+            .expect_err("quota exceeded");
+        assert!(matches!(
+            error,
+            IndexError::StudyQuotaExceeded {
+                limit: "instance count",
+                current: 1,
+                max: 1,
+                ..
+            }
+        ));
+        assert!(
+            store
+                .get_instance(second.record.identity().sop_instance_uid())
+                .await
+                .expect("get instance")
+                .is_none()
+        );
+        let study = store
+            .get_study(request.record.identity().study_instance_uid())
+            .await
+            .expect("get study")
+            .expect("study");
+        assert_eq!((study.instance_count, study.size_bytes), (1, 512));
+
+        assert_eq!(
+            store
+                .upsert_instance(
+                    request.with_blob(
+                        StoredObjectRef::new(BlobKey::new("instances/1.dcm").unwrap())
+                            .with_size_bytes(600),
+                    )
+                )
+                .await
+                .expect("replacing does not add an instance"),
+            CatalogUpsertOutcome::Updated
+        );
+    }
 }
@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use opentelemetry::metrics::Gauge;
+use tokio::fs;
+
+use crate::FilesystemBlobStore;
+
+/// Upper bounds of the entries-per-directory buckets in [`LayoutReport`].
+pub const ENTRY_COUNT_BUCKETS: [u64; 4] = [100, 1_000, 10_000, 100_000];
+
+/// Number of the most populated directories kept in [`LayoutReport`].
+const LARGEST_DIRECTORIES: usize = 10;
+
+/// Directory and file counts under a filesystem store root, for planning a
+/// move to the sharded layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutReport {
+    pub directories: u64,
+    pub files: u64,
+    /// Directories per entry-count bucket: `ENTRY_COUNT_BUCKETS[i]` bounds
+    /// bucket `i`, and the last bucket holds everything larger.
+    pub directories_by_entry_count: [u64; ENTRY_COUNT_BUCKETS.len() + 1],
+    /// Most populated directories, relative to the root, largest first.
+    pub largest_directories: Vec<(PathBuf, u64)>,
+}
+
+struct LayoutMetrics {
+    directories: Gauge<u64>,
+    files: Gauge<u64>,
+}
+
+fn layout_metrics() -> &'static LayoutMetrics {
+    static METRICS: OnceLock<LayoutMetrics> = OnceLock::new();
+
+    METRICS.get_or_init(|| {
+        let meter = opentelemetry::global::meter("rustcoon.storage.filesystem");
+
+        LayoutMetrics {
+            directories: meter.u64_gauge("storage_filesystem_directories").build(),
+            files: meter.u64_gauge("storage_filesystem_files").build(),
+        }
+    })
+}
+
+impl FilesystemBlobStore {
+    /// Walks the store root and reports how directories and files are
+    /// distributed. Staged writes are skipped. Also records the totals as the
+    /// `storage_filesystem_directories` and `storage_filesystem_files` gauges.
+    pub async fn layout_report(&self) -> std::io::Result<LayoutReport> {
+        let mut report = LayoutReport::default();
+        let mut pending = vec![self.root.clone()];
+        while let Some(directory) = pending.pop() {
+            let mut entries = fs::read_dir(&directory).await?;
+            let mut entry_count = 0_u64;
+            while let Some(entry) = entries.next_entry().await? {
+                if is_staging_file(&entry.path()) {
+                    continue;
+                }
+                entry_count += 1;
+                if entry.file_type().await?.is_dir() {
+                    pending.push(entry.path());
+                } else {
+                    report.files += 1;
+                }
+            }
+            if directory != self.root {
+                report.directories += 1;
+            }
+
+            let bucket = ENTRY_COUNT_BUCKETS
+                .iter()
+                .position(|&bound| entry_count < bound)
+                .unwrap_or(ENTRY_COUNT_BUCKETS.len());
+            report.directories_by_entry_count[bucket] += 1;
+            let relative = match directory.strip_prefix(&self.root) {
+                Ok(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => directory.clone(),
+            };
+            report.largest_directories.push((relative, entry_count));
+            report
+                .largest_directories
+                .sort_by(|(_, left), (_, right)| right.cmp(left));
+            report.largest_directories.truncate(LARGEST_DIRECTORIES);
+        }
+
+        let metrics = layout_metrics();
+        metrics.directories.record(report.directories, &[]);
+        metrics.files.record(report.files, &[]);
+        Ok(report)
+    }
+}
+
+fn is_staging_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') && name.ends_with(".staging"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempfile::tempdir;
+
+    use super::LayoutReport;
+    use crate::FilesystemBlobStore;
+
+    #[tokio::test]
+    async fn layout_report_counts_directories_files_and_their_spread() {
+        let dir = tempdir().expect("tempdir");
+        let flat = dir.path().join("instances/1.2.3/1.2.3.1");
+        let sharded = dir.path().join("instances/ab/cd/1.2.4/1.2.4.1");
+        std::fs::create_dir_all(&flat).expect("flat dirs");
+        std::fs::create_dir_all(&sharded).expect("sharded dirs");
+        for index in 0..3 {
+            std::fs::write(flat.join(format!("{index}.dcm")), b"x").expect("write");
+        }
+        std::fs::write(sharded.join("1.dcm"), b"x").expect("write");
+        std::fs::write(sharded.join(".1.dcm.abc.staging"), b"x").expect("write staging");
+
+        let report = FilesystemBlobStore::new(dir.path())
+            .layout_report()
+            .await
+            .expect("layout report");
+
+        assert_eq!(
+            report,
+            LayoutReport {
+                directories: 7,
+                files: 4,
+                directories_by_entry_count: [8, 0, 0, 0, 0],
+                largest_directories: report.largest_directories.clone(),
+            }
+        );
+        assert_eq!(
+            report.largest_directories[0],
+            (PathBuf::from("instances/1.2.3/1.2.3.1"), 3)
+        );
+    }
+}
@@ -13,6 +13,10 @@
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom};
 use uuid::Uuid;
 
+mod layout;
+
+pub use layout::{ENTRY_COUNT_BUCKETS, LayoutReport};
+
 #[derive(Debug, Clone)]
 pub struct FilesystemBlobStore {
     root: PathBuf,
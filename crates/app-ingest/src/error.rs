@@ -4,6 +4,23 @@
 
 #[derive(Debug, Error)]
 pub enum IngestError {
+    #[error("failed to read study totals for limit check: {0}")]
+    StudyLimitLookup(#[source] IndexError),
+    #[error("study {study_instance_uid} would exceed its {limit} limit ({current} of {max})")]
+    StudyLimitExceeded {
+        study_instance_uid: String,
+        limit: &'static str,
+        current: u64,
+        max: u64,
+    },
+    #[error("instance payload exceeds the {max_size_bytes} byte limit")]
+    InstanceTooLarge { max_size_bytes: u64 },
+    #[error("instance rejected by content scanner: {reason}")]
+    ContentRejected { reason: String },
+    #[error("content scan failed: {0}")]
+    ContentScan(#[source] std::io::Error),
+    #[error("failed to look up the instance being replaced: {0}")]
+    ExistingInstanceLookup(#[source] IndexError),
     #[error("failed to resolve blob key: {0}")]
     BlobKey(#[source] BlobKeyError),
     #[error("failed to begin blob write: {0}")]
@@ -14,6 +14,7 @@ struct IngestMetrics {
     instances_total: Counter<u64>,
     duration_seconds: Histogram<f64>,
     payload_bytes: Histogram<u64>,
+    content_scans_total: Counter<u64>,
 }
 
 fn meter() -> Meter {
@@ -33,6 +34,7 @@ fn ingest_metrics() -> &'static IngestMetrics {
                 .with_unit("s")
                 .build(),
             payload_bytes: meter.u64_histogram("ingest_payload_bytes").build(),
+            content_scans_total: meter.u64_counter("ingest_content_scans_total").build(),
         }
     })
 }
@@ -58,6 +60,18 @@ pub(crate) fn existing_instance_span(record: &DicomInstanceRecord) -> Span {
     )
 }
 
+pub(crate) fn content_scan_span(record: &DicomInstanceRecord) -> Span {
+    info_span!(
+        "rustcoon.ingest.content_scan",
+        sop_instance_uid = record.identity().sop_instance_uid().as_str(),
+        outcome = field::Empty,
+    )
+}
+
+pub(crate) fn catalog_study_limits_span() -> Span {
+    info_span!("rustcoon.ingest.catalog.study_limits")
+}
+
 pub(crate) fn blob_begin_write_span() -> Span {
     info_span!("rustcoon.ingest.blob.begin_write")
 }
@@ -66,6 +80,10 @@ pub(crate) fn blob_write_payload_span() -> Span {
     info_span!("rustcoon.ingest.blob.write_payload")
 }
 
+pub(crate) fn validate_payload_span() -> Span {
+    info_span!("rustcoon.ingest.validate_payload")
+}
+
 pub(crate) fn blob_abort_write_span() -> Span {
     info_span!("rustcoon.ingest.blob.abort_write")
 }
@@ -82,6 +100,10 @@ pub(crate) fn catalog_upsert_instance_span() -> Span {
     info_span!("rustcoon.ingest.catalog.upsert_instance")
 }
 
+pub(crate) fn catalog_preview_upsert_span() -> Span {
+    info_span!("rustcoon.ingest.catalog.preview_upsert")
+}
+
 pub(crate) fn blob_rollback_delete_span() -> Span {
     info_span!("rustcoon.ingest.blob.rollback_delete")
 }
@@ -118,6 +140,13 @@ pub(crate) fn record_ingest_success(outcome: &'static str, duration: Duration, s
         .record(size_bytes, &[KeyValue::new("ingest.outcome", outcome)]);
 }
 
+pub(crate) fn record_content_scan(outcome: &'static str) {
+    Span::current().record("outcome", outcome);
+    ingest_metrics()
+        .content_scans_total
+        .add(1, &[KeyValue::new("outcome", outcome)]);
+}
+
 pub(crate) fn record_ingest_failure(error: &IngestError, duration: Duration) {
     let error_kind = ingest_error_kind(error);
     ingest_metrics().instances_total.add(
@@ -138,6 +167,12 @@ pub(crate) fn record_ingest_failure(error: &IngestError, duration: Duration) {
 
 fn ingest_error_kind(error: &IngestError) -> &'static str {
     match error {
+        IngestError::StudyLimitLookup(_) => "study_limit_lookup",
+        IngestError::StudyLimitExceeded { .. } => "study_limit_exceeded",
+        IngestError::InstanceTooLarge { .. } => "instance_too_large",
+        IngestError::ContentRejected { .. } => "content_rejected",
+        IngestError::ContentScan(_) => "content_scan",
+        IngestError::ExistingInstanceLookup(_) => "existing_instance_lookup",
         IngestError::BlobKey(_) => "blob_key",
         IngestError::BeginWrite(_) => "begin_write",
         IngestError::ReadPayload(_) => "read_payload",
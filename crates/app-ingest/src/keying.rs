@@ -10,13 +10,18 @@ pub trait BlobKeyResolver: Send + Sync {
 pub struct HierarchicalInstanceKeyResolver {
     prefix: String,
     extension: String,
+    shard_levels: u8,
 }
 
 impl HierarchicalInstanceKeyResolver {
+    /// Deepest supported hash-prefix nesting.
+    pub const MAX_SHARD_LEVELS: u8 = 4;
+
     pub fn new() -> Self {
         Self {
             prefix: "instances".to_string(),
             extension: "dcm".to_string(),
+            shard_levels: 0,
         }
     }
 
@@ -29,14 +34,31 @@ pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
         self.extension = extension.into();
         self
     }
+
+    /// Nests study directories under `levels` two-hex-digit segments derived
+    /// from the Study Instance UID, so no single directory collects every
+    /// study. Instances already on record keep their key when re-stored.
+    pub fn with_shard_levels(mut self, levels: u8) -> Self {
+        self.shard_levels = levels.min(Self::MAX_SHARD_LEVELS);
+        self
+    }
+
+    fn shard_prefix(&self, study_instance_uid: &str) -> String {
+        let hash = fnv1a_32(study_instance_uid.as_bytes()).to_be_bytes();
+        hash[..usize::from(self.shard_levels)]
+            .iter()
+            .map(|byte| format!("{byte:02x}/"))
+            .collect()
+    }
 }
 
 impl BlobKeyResolver for HierarchicalInstanceKeyResolver {
     fn resolve(&self, record: &DicomInstanceRecord) -> Result<BlobKey, BlobKeyError> {
         let identity = record.identity();
         BlobKey::new(format!(
-            "{}/{}/{}/{}.{}",
+            "{}/{}{}/{}/{}.{}",
             self.prefix,
+            self.shard_prefix(identity.study_instance_uid().as_str()),
             identity.study_instance_uid().as_str(),
             identity.series_instance_uid().as_str(),
             identity.sop_instance_uid().as_str(),
@@ -45,6 +67,13 @@ fn resolve(&self, record: &DicomInstanceRecord) -> Result<BlobKey, BlobKeyError>
     }
 }
 
+/// 32-bit FNV-1a; stable across builds, unlike the std hasher.
+fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use rustcoon_dicom::{
@@ -89,4 +118,16 @@ fn hierarchical_resolver_supports_custom_prefix_and_extension() {
 
         assert_eq!(key.as_str(), "archive/1.2.3/1.2.3.4/1.2.3.4.5.bin");
     }
+
+    #[test]
+    fn hierarchical_resolver_shards_studies_by_uid_hash() {
+        let resolver = HierarchicalInstanceKeyResolver::new().with_shard_levels(2);
+        let key = resolver.resolve(&sample_record()).expect("key");
+
+        assert_eq!(key.as_str(), "instances/18/bc/1.2.3/1.2.3.4/1.2.3.4.5.dcm");
+
+        let resolver = HierarchicalInstanceKeyResolver::new().with_shard_levels(9);
+        let key = resolver.resolve(&sample_record()).expect("key");
+        assert_eq!(key.as_str().split('/').count(), 8);
+    }
 }
@@ -7,9 +7,13 @@
 mod instrumentation;
 mod keying;
 mod model;
+mod scanning;
 mod service;
 
 pub use error::IngestError;
 pub use keying::{BlobKeyResolver, HierarchicalInstanceKeyResolver};
-pub use model::{IngestOutcome, IngestRequest, IngestResult};
+pub use model::{
+    IngestOutcome, IngestRequest, IngestResult, ReplacementPolicy, StudyLimit, StudyLimits,
+};
+pub use scanning::{ContentScanner, ScanVerdict};
 pub use service::IngestService;
@@ -1,4 +1,6 @@
-use rustcoon_dicom::DicomInstanceRecord;
+use std::collections::HashMap;
+
+use rustcoon_dicom::{DicomInstanceRecord, StudyInstanceUid};
 use rustcoon_index::{DicomAttributeDocument, StoredObjectRef};
 use rustcoon_storage::{BlobWritePrecondition, DurabilityHint};
 
@@ -11,6 +13,8 @@ pub struct IngestRequest {
     pub precondition: BlobWritePrecondition,
     pub content_type: String,
     pub durability: Option<DurabilityHint>,
+    /// Runs every ingest check without writing the blob or the catalog entry.
+    pub validate_only: bool,
 }
 
 impl IngestRequest {
@@ -21,6 +25,7 @@ pub fn new(record: DicomInstanceRecord) -> Self {
             precondition: BlobWritePrecondition::None,
             content_type: DEFAULT_CONTENT_TYPE.to_string(),
             durability: None,
+            validate_only: false,
         }
     }
 
@@ -43,6 +48,11 @@ pub fn with_durability(mut self, durability: DurabilityHint) -> Self {
         self.durability = Some(durability);
         self
     }
+
+    pub fn with_validate_only(mut self, validate_only: bool) -> Self {
+        self.validate_only = validate_only;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,6 +68,61 @@ pub struct IngestResult {
     pub blob: StoredObjectRef,
 }
 
+/// How a re-stored instance treats the blob already on record.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplacementPolicy {
+    /// Write over the existing blob at the same key.
+    #[default]
+    Overwrite,
+    /// Write each replacement to a new versioned key and leave earlier blobs
+    /// in place; the catalog points at the latest one and lists the earlier
+    /// ones as superseded, still counted in the study size.
+    KeepVersions,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StudyLimit {
+    pub max_instances: Option<u64>,
+    pub max_size_bytes: Option<u64>,
+}
+
+impl StudyLimit {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_instances.is_none() && self.max_size_bytes.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StudyLimits {
+    default: StudyLimit,
+    overrides: HashMap<StudyInstanceUid, StudyLimit>,
+}
+
+impl StudyLimits {
+    pub fn new(default: StudyLimit) -> Self {
+        Self {
+            default,
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn with_override(
+        mut self,
+        study_instance_uid: StudyInstanceUid,
+        limit: StudyLimit,
+    ) -> Self {
+        self.overrides.insert(study_instance_uid, limit);
+        self
+    }
+
+    pub fn for_study(&self, study_instance_uid: &StudyInstanceUid) -> StudyLimit {
+        self.overrides
+            .get(study_instance_uid)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use dicom_core::{DataElement, PrimitiveValue, VR};
@@ -69,7 +134,7 @@ mod tests {
     use rustcoon_index::StoredObjectRef;
     use rustcoon_storage::{BlobKey, BlobWritePrecondition, DurabilityHint};
 
-    use super::{IngestOutcome, IngestRequest, IngestResult};
+    use super::{IngestOutcome, IngestRequest, IngestResult, StudyLimit, StudyLimits};
 
     fn sample_record() -> DicomInstanceRecord {
         let identity = DicomInstanceIdentity::new(
@@ -101,6 +166,7 @@ fn ingest_request_defaults_match_archive_ingest_expectations() {
         assert_eq!(request.precondition, BlobWritePrecondition::None);
         assert_eq!(request.content_type, "application/dicom");
         assert_eq!(request.durability, None);
+        assert!(!request.validate_only);
     }
 
     #[test]
@@ -116,13 +182,15 @@ fn ingest_request_builders_override_optional_fields() {
             .with_attributes(attributes.clone())
             .with_precondition(BlobWritePrecondition::MustNotExist)
             .with_content_type("application/octet-stream")
-            .with_durability(DurabilityHint::Replicated);
+            .with_durability(DurabilityHint::Replicated)
+            .with_validate_only(true);
 
         assert_eq!(request.record, record);
         assert_eq!(request.attributes, attributes);
         assert_eq!(request.precondition, BlobWritePrecondition::MustNotExist);
         assert_eq!(request.content_type, "application/octet-stream");
         assert_eq!(request.durability, Some(DurabilityHint::Replicated));
+        assert!(request.validate_only);
     }
 
     #[test]
@@ -138,4 +206,29 @@ fn ingest_result_exposes_outcome_and_blob_reference() {
         assert_eq!(result.outcome, IngestOutcome::Updated);
         assert_eq!(result.blob, blob);
     }
+
+    #[test]
+    fn study_limits_prefer_per_study_overrides() {
+        let default = StudyLimit {
+            max_instances: Some(10),
+            max_size_bytes: None,
+        };
+        let raised = StudyLimit {
+            max_instances: Some(1_000),
+            max_size_bytes: Some(1 << 30),
+        };
+        let limits = StudyLimits::new(default)
+            .with_override(StudyInstanceUid::new("1.2.3").unwrap(), raised);
+
+        assert_eq!(
+            limits.for_study(&StudyInstanceUid::new("1.2.3").unwrap()),
+            raised
+        );
+        assert_eq!(
+            limits.for_study(&StudyInstanceUid::new("1.2.4").unwrap()),
+            default
+        );
+        assert!(StudyLimit::default().is_unlimited());
+        assert!(!default.is_unlimited());
+    }
 }
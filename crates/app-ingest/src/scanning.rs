@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use rustcoon_dicom::DicomInstanceRecord;
+
+/// Result of inspecting a received payload before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// The scanner chose not to inspect this payload, e.g. an exempt SOP class.
+    Skipped,
+    Rejected {
+        reason: String,
+    },
+}
+
+/// External inspection (virus or content policy) of a received payload file.
+#[async_trait]
+pub trait ContentScanner: Send + Sync {
+    async fn scan(
+        &self,
+        record: &DicomInstanceRecord,
+        payload: &Path,
+    ) -> Result<ScanVerdict, std::io::Error>;
+}
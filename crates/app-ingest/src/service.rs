@@ -1,18 +1,24 @@
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+use rustcoon_dicom::DicomInstanceRecord;
 use rustcoon_index::{
-    CatalogInstanceEntry, CatalogReadStore, CatalogUpsertOutcome, CatalogWriteStore,
-    InstanceUpsertRequest, StoredObjectRef,
+    CatalogInstanceEntry, CatalogReadStore, CatalogUpsertOutcome, CatalogWriteStore, IndexError,
+    InstanceUpsertRequest, StoredObjectRef, StudyQuota, check_hierarchy_unchanged,
+};
+use rustcoon_storage::{
+    BlobKey, BlobKeyError, BlobStore, BlobWritePrecondition, BlobWriteRequest, BlobWriteSession,
+    DurabilityHint,
 };
-use rustcoon_storage::{BlobStore, BlobWriteRequest, BlobWriteSession, DurabilityHint};
 use tokio::io::{AsyncRead, AsyncReadExt};
 use tracing::Instrument;
 
 use crate::error::IngestError;
 use crate::instrumentation;
 use crate::keying::BlobKeyResolver;
-use crate::model::{IngestOutcome, IngestRequest, IngestResult};
+use crate::model::{IngestOutcome, IngestRequest, IngestResult, ReplacementPolicy, StudyLimits};
+use crate::scanning::{ContentScanner, ScanVerdict};
 
 const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
 
@@ -22,6 +28,11 @@ pub struct IngestService {
     catalog_write: Arc<dyn CatalogWriteStore>,
     key_resolver: Arc<dyn BlobKeyResolver>,
     chunk_size: usize,
+    study_limits: StudyLimits,
+    max_instance_size_bytes: Option<u64>,
+    replacement_policy: ReplacementPolicy,
+    validate_only: bool,
+    content_scanner: Option<Arc<dyn ContentScanner>>,
 }
 
 impl IngestService {
@@ -37,6 +48,11 @@ pub fn new(
             catalog_write,
             key_resolver,
             chunk_size: DEFAULT_CHUNK_SIZE,
+            study_limits: StudyLimits::default(),
+            max_instance_size_bytes: None,
+            replacement_policy: ReplacementPolicy::default(),
+            validate_only: false,
+            content_scanner: None,
         }
     }
 
@@ -45,6 +61,69 @@ pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
         self
     }
 
+    pub fn with_study_limits(mut self, study_limits: StudyLimits) -> Self {
+        self.study_limits = study_limits;
+        self
+    }
+
+    /// Rejects any single instance payload larger than `max_size_bytes`.
+    pub fn with_max_instance_size_bytes(mut self, max_size_bytes: u64) -> Self {
+        self.max_instance_size_bytes = Some(max_size_bytes);
+        self
+    }
+
+    pub fn max_instance_size_bytes(&self) -> Option<u64> {
+        self.max_instance_size_bytes
+    }
+
+    pub fn with_replacement_policy(mut self, replacement_policy: ReplacementPolicy) -> Self {
+        self.replacement_policy = replacement_policy;
+        self
+    }
+
+    /// Treats every request as validate-only, so nothing is ever persisted.
+    pub fn with_validate_only(mut self, validate_only: bool) -> Self {
+        self.validate_only = validate_only;
+        self
+    }
+
+    pub fn with_content_scanner(mut self, scanner: Arc<dyn ContentScanner>) -> Self {
+        self.content_scanner = Some(scanner);
+        self
+    }
+
+    /// Runs the configured content scanner over a received payload file.
+    /// Protocol layers that buffer payloads to disk call this before
+    /// [`Self::ingest`]; without a scanner every payload passes.
+    pub async fn scan_payload(
+        &self,
+        record: &DicomInstanceRecord,
+        payload: &Path,
+    ) -> Result<(), IngestError> {
+        let Some(scanner) = &self.content_scanner else {
+            return Ok(());
+        };
+
+        async {
+            let verdict = scanner.scan(record, payload).await;
+            instrumentation::record_content_scan(match &verdict {
+                Ok(ScanVerdict::Clean) => "clean",
+                Ok(ScanVerdict::Skipped) => "skipped",
+                Ok(ScanVerdict::Rejected { .. }) => "rejected",
+                Err(_) => "error",
+            });
+            match verdict {
+                Ok(ScanVerdict::Clean | ScanVerdict::Skipped) => Ok(()),
+                Ok(ScanVerdict::Rejected { reason }) => {
+                    Err(IngestError::ContentRejected { reason })
+                }
+                Err(error) => Err(IngestError::ContentScan(error)),
+            }
+        }
+        .instrument(instrumentation::content_scan_span(record))
+        .await
+    }
+
     pub async fn ingest<R>(
         &self,
         request: IngestRequest,
@@ -55,19 +134,42 @@ pub async fn ingest<R>(
     {
         let span = instrumentation::instance_span(&request.record);
         let started_at = Instant::now();
+        let validate_only = self.validate_only || request.validate_only;
 
         let result = async move {
+            let existing = self
+                .existing_instance(&request)
+                .await
+                .map_err(IngestError::ExistingInstanceLookup)?;
+            self.ensure_hierarchy_unchanged(&request, existing.as_ref())
+                .await?;
+            let study_size_budget = self
+                .check_study_limits(&request, existing.as_ref())
+                .instrument(instrumentation::catalog_study_limits_span())
+                .await?;
+
             let key = self
                 .key_resolver
                 .resolve(&request.record)
                 .map_err(IngestError::BlobKey)?;
+            let (key, precondition) =
+                match self.replacement_key(&request, existing.as_ref(), &key)? {
+                    Some(replacement) => replacement,
+                    None => (key, request.precondition),
+                };
             instrumentation::record_blob_key(&key);
 
+            if validate_only {
+                return self
+                    .validate_payload(&request, key, study_size_budget.as_ref(), reader)
+                    .await;
+            }
+
             let mut session = self
                 .storage
                 .begin_write(
                     BlobWriteRequest::new(key.clone())
-                        .with_precondition(request.precondition)
+                        .with_precondition(precondition)
                         .with_content_type(request.content_type)
                         .with_durability(request.durability.unwrap_or(DurabilityHint::Durable)),
                 )
@@ -76,7 +178,7 @@ pub async fn ingest<R>(
                 .map_err(IngestError::BeginWrite)?;
 
             let write_result = self
-                .write_payload(&mut *session, reader)
+                .write_payload(Some(&mut *session), study_size_budget.as_ref(), reader)
                 .instrument(instrumentation::blob_write_payload_span())
                 .await;
             if let Err(error) = write_result {
@@ -109,9 +211,18 @@ pub async fn ingest<R>(
                 blob = blob.with_version(version);
             }
 
-            let index_request = InstanceUpsertRequest::new(request.record.clone())
+            let mut index_request = InstanceUpsertRequest::new(request.record.clone())
                 .with_attributes(request.attributes)
                 .with_blob(blob.clone());
+            let limit = self
+                .study_limits
+                .for_study(request.record.identity().study_instance_uid());
+            if !limit.is_unlimited() {
+                index_request = index_request.with_study_quota(StudyQuota {
+                    max_instances: limit.max_instances,
+                    max_size_bytes: limit.max_size_bytes,
+                });
+            }
 
             match self
                 .catalog_write
@@ -125,15 +236,37 @@ pub async fn ingest<R>(
                     Ok(IngestResult { outcome, blob })
                 }
                 Err(source) => {
-                    let rollback_failed = self
-                        .storage
-                        .delete(&key)
-                        .instrument(instrumentation::blob_rollback_delete_span())
-                        .await
-                        .err();
-                    Err(IngestError::CatalogUpdate {
-                        source,
-                        rollback_failed,
+                    // The recorded key is still what the catalog points at;
+                    // deleting it would leave the instance without a blob.
+                    let recorded = existing
+                        .as_ref()
+                        .and_then(|entry| entry.blob.as_ref())
+                        .is_some_and(|blob| blob.key == key);
+                    let rollback_failed = if recorded {
+                        None
+                    } else {
+                        self.storage
+                            .delete(&key)
+                            .instrument(instrumentation::blob_rollback_delete_span())
+                            .await
+                            .err()
+                    };
+                    Err(match source {
+                        IndexError::StudyQuotaExceeded {
+                            study_instance_uid,
+                            limit,
+                            current,
+                            max,
+                        } if rollback_failed.is_none() => IngestError::StudyLimitExceeded {
+                            study_instance_uid,
+                            limit,
+                            current,
+                            max,
+                        },
+                        source => IngestError::CatalogUpdate {
+                            source,
+                            rollback_failed,
+                        },
                     })
                 }
             }
@@ -142,6 +275,7 @@ pub async fn ingest<R>(
         .await;
 
         match &result {
+            Ok(_) if validate_only => {}
             Ok(result) => instrumentation::record_ingest_success(
                 result.outcome.label(),
                 started_at.elapsed(),
@@ -164,15 +298,185 @@ pub async fn existing_instance(
             .await
     }
 
+    /// Rejects objects that would move a recorded series or instance to
+    /// another parent before any blob is written. The catalog repeats the
+    /// check when the write lands.
+    async fn ensure_hierarchy_unchanged(
+        &self,
+        request: &IngestRequest,
+        existing: Option<&CatalogInstanceEntry>,
+    ) -> Result<(), IngestError> {
+        let identity = request.record.identity();
+        let series = self
+            .index
+            .get_series(identity.series_instance_uid())
+            .await
+            .map_err(IngestError::ExistingInstanceLookup)?;
+        let series_study = series
+            .as_ref()
+            .map(|entry| entry.record.identity().study_instance_uid().as_str());
+        let instance_parents = existing.map(|entry| {
+            let recorded = entry.record.identity();
+            (
+                recorded.study_instance_uid().as_str(),
+                recorded.series_instance_uid().as_str(),
+            )
+        });
+        check_hierarchy_unchanged(identity, series_study, instance_parents).map_err(|source| {
+            IngestError::CatalogUpdate {
+                source,
+                rollback_failed: None,
+            }
+        })
+    }
+
+    /// Picks the key for an instance that already has a blob on record.
+    /// Under [`ReplacementPolicy::Overwrite`] that is the recorded key, so a
+    /// changed key layout never orphans the earlier file, but only when the
+    /// incoming object sits in the recorded study and series; anything else
+    /// is written to its own `key` and left to the catalog to accept. Under
+    /// [`ReplacementPolicy::KeepVersions`] it is a fresh versioned `key`.
+    fn replacement_key(
+        &self,
+        request: &IngestRequest,
+        existing: Option<&CatalogInstanceEntry>,
+        key: &BlobKey,
+    ) -> Result<Option<(BlobKey, BlobWritePrecondition)>, IngestError> {
+        let Some(entry) = existing else {
+            return Ok(None);
+        };
+        let Some(current) = entry.blob.as_ref() else {
+            return Ok(None);
+        };
+
+        match self.replacement_policy {
+            ReplacementPolicy::Overwrite => {
+                let recorded = entry.record.identity();
+                let incoming = request.record.identity();
+                if recorded.study_instance_uid() != incoming.study_instance_uid()
+                    || recorded.series_instance_uid() != incoming.series_instance_uid()
+                {
+                    return Ok(None);
+                }
+                Ok(Some((current.key.clone(), BlobWritePrecondition::None)))
+            }
+            ReplacementPolicy::KeepVersions => {
+                let version = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_nanos());
+                let key = versioned_key(key, version).map_err(IngestError::BlobKey)?;
+                Ok(Some((key, BlobWritePrecondition::MustNotExist)))
+            }
+        }
+    }
+
+    /// Reads the payload against the size limit and asks the catalog for the
+    /// outcome a real ingest would have had, without touching storage or the
+    /// catalog. The blob version is unknown until written, so stores that
+    /// version every write report re-stores as updated, as they would be.
+    async fn validate_payload<R>(
+        &self,
+        request: &IngestRequest,
+        key: BlobKey,
+        study_size_budget: Option<&StudySizeBudget>,
+        reader: &mut R,
+    ) -> Result<IngestResult, IngestError>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        let size_bytes = self
+            .write_payload(None, study_size_budget, reader)
+            .instrument(instrumentation::validate_payload_span())
+            .await?;
+        instrumentation::record_blob_size(size_bytes);
+
+        let blob = StoredObjectRef::new(key).with_size_bytes(size_bytes);
+        let preview_request = InstanceUpsertRequest::new(request.record.clone())
+            .with_attributes(request.attributes.clone())
+            .with_blob(blob.clone());
+        let outcome = self
+            .catalog_write
+            .preview_upsert(&preview_request)
+            .instrument(instrumentation::catalog_preview_upsert_span())
+            .await
+            .map_err(|source| IngestError::CatalogUpdate {
+                source,
+                rollback_failed: None,
+            })?;
+        instrumentation::record_outcome("validated");
+
+        Ok(IngestResult {
+            outcome: map_upsert_outcome(outcome),
+            blob,
+        })
+    }
+
+    /// Rejects a new instance once the study's recorded instance count reaches
+    /// its limit, and returns the size budget the incoming payload is checked
+    /// against while it is read. Replacing an existing instance does not add
+    /// to the count, and under [`ReplacementPolicy::Overwrite`] its stored
+    /// size is freed from the budget. Senders racing on one study can all
+    /// pass this check; the catalog re-checks the limits in the upsert.
+    async fn check_study_limits(
+        &self,
+        request: &IngestRequest,
+        existing: Option<&CatalogInstanceEntry>,
+    ) -> Result<Option<StudySizeBudget>, IngestError> {
+        let study_instance_uid = request.record.identity().study_instance_uid();
+        let limit = self.study_limits.for_study(study_instance_uid);
+        if limit.is_unlimited() {
+            return Ok(None);
+        }
+
+        let Some(study) = self
+            .index
+            .get_study(study_instance_uid)
+            .await
+            .map_err(IngestError::StudyLimitLookup)?
+        else {
+            return Ok(limit.max_size_bytes.map(|max| StudySizeBudget {
+                study_instance_uid: study_instance_uid.to_string(),
+                stored_bytes: 0,
+                max,
+            }));
+        };
+        if let Some(max) = limit.max_instances
+            && existing.is_none()
+            && study.instance_count >= max
+        {
+            return Err(IngestError::StudyLimitExceeded {
+                study_instance_uid: study_instance_uid.to_string(),
+                limit: "instance count",
+                current: study.instance_count,
+                max,
+            });
+        }
+
+        let replaced_bytes = existing
+            .filter(|_| self.replacement_policy == ReplacementPolicy::Overwrite)
+            .and_then(|entry| entry.blob.as_ref())
+            .and_then(|blob| blob.size_bytes)
+            .unwrap_or(0);
+        Ok(limit.max_size_bytes.map(|max| StudySizeBudget {
+            study_instance_uid: study_instance_uid.to_string(),
+            stored_bytes: study.size_bytes.saturating_sub(replaced_bytes),
+            max,
+        }))
+    }
+
+    /// Streams the payload into `session`, or only reads and measures it when
+    /// no session is given. Returns the number of bytes read.
     async fn write_payload<R>(
         &self,
-        session: &mut dyn BlobWriteSession,
+        mut session: Option<&mut dyn BlobWriteSession>,
+        study_size_budget: Option<&StudySizeBudget>,
         reader: &mut R,
-    ) -> Result<(), IngestError>
+    ) -> Result<u64, IngestError>
     where
         R: AsyncRead + Unpin + Send,
     {
         let mut buffer = vec![0; self.chunk_size];
+        let mut written = 0_u64;
 
         loop {
             let read = reader
@@ -183,12 +487,48 @@ async fn write_payload<R>(
                 break;
             }
 
-            session
-                .write_chunk(&buffer[..read])
-                .await
-                .map_err(IngestError::WritePayload)?;
+            written += read as u64;
+            if let Some(max_size_bytes) = self
+                .max_instance_size_bytes
+                .filter(|max_size_bytes| written > *max_size_bytes)
+            {
+                return Err(IngestError::InstanceTooLarge { max_size_bytes });
+            }
+            if let Some(budget) = study_size_budget {
+                budget.check(written)?;
+            }
+
+            if let Some(session) = session.as_deref_mut() {
+                session
+                    .write_chunk(&buffer[..read])
+                    .await
+                    .map_err(IngestError::WritePayload)?;
+            }
         }
 
+        Ok(written)
+    }
+}
+
+/// Study size still available to an incoming payload.
+#[derive(Debug)]
+struct StudySizeBudget {
+    study_instance_uid: String,
+    stored_bytes: u64,
+    max: u64,
+}
+
+impl StudySizeBudget {
+    fn check(&self, incoming_bytes: u64) -> Result<(), IngestError> {
+        let total = self.stored_bytes.saturating_add(incoming_bytes);
+        if total > self.max {
+            return Err(IngestError::StudyLimitExceeded {
+                study_instance_uid: self.study_instance_uid.clone(),
+                limit: "size",
+                current: total,
+                max: self.max,
+            });
+        }
         Ok(())
     }
 }
@@ -203,6 +543,19 @@ fn label(self) -> &'static str {
     }
 }
 
+/// Inserts `.v<version>` before the file extension of the key's last segment.
+fn versioned_key(key: &BlobKey, version: u128) -> Result<BlobKey, BlobKeyError> {
+    let value = key.as_str();
+    let name_start = value.rfind('/').map_or(0, |index| index + 1);
+    match value[name_start..].rfind('.').filter(|dot| *dot > 0) {
+        Some(dot) => {
+            let (stem, extension) = value.split_at(name_start + dot);
+            BlobKey::new(format!("{stem}.v{version}{extension}"))
+        }
+        None => BlobKey::new(format!("{value}.v{version}")),
+    }
+}
+
 fn map_upsert_outcome(outcome: CatalogUpsertOutcome) -> IngestOutcome {
     match outcome {
         CatalogUpsertOutcome::Created => IngestOutcome::Created,
@@ -223,7 +576,8 @@ mod tests {
     use dicom_object::InMemDicomObject;
     use rustcoon_dicom::{
         DicomInstanceIdentity, DicomInstanceRecord, DicomPatient, DicomSeriesMetadata,
-        DicomStudyMetadata, SeriesInstanceUid, SopClassUid, SopInstanceUid, StudyInstanceUid,
+        DicomStudyIdentity, DicomStudyMetadata, DicomStudyRecord, SeriesInstanceUid, SopClassUid,
+        SopInstanceUid, StudyInstanceUid,
     };
     use rustcoon_index::{
         CatalogInstanceEntry, CatalogQuery, CatalogQueryEntry, CatalogReadStore,
@@ -236,9 +590,10 @@ mod tests {
         DurabilityHint, StorageError,
     };
 
-    use super::IngestService;
+    use super::{IngestService, versioned_key};
     use crate::keying::HierarchicalInstanceKeyResolver;
-    use crate::model::{IngestOutcome, IngestRequest};
+    use crate::model::{IngestOutcome, IngestRequest, ReplacementPolicy, StudyLimit, StudyLimits};
+    use crate::scanning::{ContentScanner, ScanVerdict};
 
     #[derive(Default)]
     struct State {
@@ -247,6 +602,10 @@ struct State {
         deleted: Vec<String>,
         index_requests: Vec<rustcoon_index::InstanceUpsertRequest>,
         write_requests: Vec<BlobWriteRequest>,
+        study_counters: Option<(u64, u64)>,
+        /// Study totals the upsert transaction sees, when other writes landed
+        /// after the early limit check.
+        upsert_counters: Option<(u64, u64)>,
     }
 
     struct MockBlobStore {
@@ -413,9 +772,20 @@ struct MockCatalog {
     impl CatalogReadStore for MockCatalog {
         async fn get_study(
             &self,
-            _study_instance_uid: &StudyInstanceUid,
+            study_instance_uid: &StudyInstanceUid,
         ) -> Result<Option<CatalogStudyEntry>, IndexError> {
-            Ok(None)
+            let state = self.state.lock().expect("state lock");
+            Ok(state
+                .study_counters
+                .map(|(instance_count, size_bytes)| CatalogStudyEntry {
+                    record: DicomStudyRecord::new(
+                        DicomStudyIdentity::new(study_instance_uid.clone()),
+                        DicomPatient::default(),
+                        DicomStudyMetadata::default(),
+                    ),
+                    instance_count,
+                    size_bytes,
+                }))
         }
 
         async fn get_series(
@@ -448,6 +818,13 @@ async fn query(&self, _query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, I
                 Some(0),
             ))
         }
+
+        async fn list_superseded_blobs(
+            &self,
+            _sop_instance_uid: &SopInstanceUid,
+        ) -> Result<Vec<StoredObjectRef>, IndexError> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -463,14 +840,41 @@ async fn upsert_instance(
                 ));
             }
 
-            self.state
-                .lock()
-                .expect("state lock")
-                .index_requests
-                .push(request);
+            let mut state = self.state.lock().expect("state lock");
+            if let (Some(quota), Some((instance_count, size_bytes))) =
+                (request.study_quota, state.upsert_counters)
+            {
+                let added = request
+                    .blob
+                    .as_ref()
+                    .and_then(|blob| blob.size_bytes)
+                    .unwrap_or(0);
+                quota.check(
+                    request.record.identity().study_instance_uid().as_str(),
+                    (instance_count + 1, size_bytes + added),
+                    (1, added as i64),
+                )?;
+            }
+            state.index_requests.push(request);
             Ok(self.outcome)
         }
 
+        async fn preview_upsert(
+            &self,
+            request: &rustcoon_index::InstanceUpsertRequest,
+        ) -> Result<CatalogUpsertOutcome, IndexError> {
+            let state = self.state.lock().expect("state lock");
+            let recorded = state.index_requests.iter().any(|recorded| {
+                recorded.record.identity().sop_instance_uid()
+                    == request.record.identity().sop_instance_uid()
+            });
+            Ok(match (recorded, self.outcome) {
+                (false, _) => CatalogUpsertOutcome::Created,
+                (true, CatalogUpsertOutcome::Unchanged) => CatalogUpsertOutcome::Unchanged,
+                (true, _) => CatalogUpsertOutcome::Updated,
+            })
+        }
+
         async fn attach_blob(
             &self,
             _identity: &rustcoon_dicom::DicomInstanceIdentity,
@@ -510,6 +914,19 @@ fn sample_request() -> IngestRequest {
             .with_precondition(BlobWritePrecondition::MustNotExist)
     }
 
+    struct FixedScanner(Result<ScanVerdict, std::io::ErrorKind>);
+
+    #[async_trait]
+    impl ContentScanner for FixedScanner {
+        async fn scan(
+            &self,
+            _record: &DicomInstanceRecord,
+            _payload: &std::path::Path,
+        ) -> Result<ScanVerdict, std::io::Error> {
+            self.0.clone().map_err(std::io::Error::from)
+        }
+    }
+
     struct FailingReader;
 
     impl tokio::io::AsyncRead for FailingReader {
@@ -579,6 +996,389 @@ async fn ingest_streams_payload_and_updates_catalog() {
         );
     }
 
+    #[tokio::test]
+    async fn overwrite_reuses_the_recorded_key_after_layout_changes() {
+        let existing = rustcoon_index::InstanceUpsertRequest::new(sample_record()).with_blob(
+            StoredObjectRef::new(BlobKey::new("instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm").unwrap()),
+        );
+        let state = Arc::new(Mutex::new(State {
+            index_requests: vec![existing],
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Updated,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new().with_shard_levels(2)),
+        );
+
+        let result = service
+            .ingest(sample_request(), &mut Cursor::new(b"second".to_vec()))
+            .await
+            .expect("re-store");
+
+        assert_eq!(
+            result.blob.key.as_str(),
+            "instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm"
+        );
+        let state = state.lock().expect("state lock");
+        assert_eq!(
+            state.write_requests[0].precondition,
+            BlobWritePrecondition::None
+        );
+    }
+
+    #[tokio::test]
+    async fn overwrite_never_deletes_the_recorded_blob_on_catalog_failure() {
+        let recorded_key = "instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm";
+        let existing = rustcoon_index::InstanceUpsertRequest::new(sample_record())
+            .with_blob(StoredObjectRef::new(BlobKey::new(recorded_key).unwrap()));
+        let state = Arc::new(Mutex::new(State {
+            index_requests: vec![existing],
+            ..State::default()
+        }));
+        state
+            .lock()
+            .expect("state lock")
+            .blobs
+            .insert(recorded_key.to_string(), b"original".to_vec());
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Updated,
+            fail_upsert: true,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        );
+
+        let moved = DicomInstanceRecord::new(
+            DicomInstanceIdentity::new(
+                StudyInstanceUid::new("1.2.9").unwrap(),
+                SeriesInstanceUid::new("1.2.9.1").unwrap(),
+                SopInstanceUid::new("1.2.3.1.1").unwrap(),
+                SopClassUid::new("1.2.840.10008.5.1.4.1.1.2").unwrap(),
+            ),
+            DicomPatient::default(),
+            DicomStudyMetadata::default(),
+            DicomSeriesMetadata::default(),
+            rustcoon_dicom::DicomInstanceMetadata::default(),
+        );
+        let mut request = sample_request();
+        request.record = moved;
+        service
+            .ingest(request, &mut Cursor::new(b"other study".to_vec()))
+            .await
+            .expect_err("catalog failure");
+        {
+            let state = state.lock().expect("state lock");
+            assert_eq!(state.blobs.get(recorded_key).expect("kept"), b"original");
+            assert!(!state.deleted.iter().any(|key| key == recorded_key));
+        }
+
+        service
+            .ingest(sample_request(), &mut Cursor::new(b"same study".to_vec()))
+            .await
+            .expect_err("catalog failure");
+        let state = state.lock().expect("state lock");
+        assert!(state.blobs.contains_key(recorded_key));
+        assert!(!state.deleted.iter().any(|key| key == recorded_key));
+    }
+
+    #[tokio::test]
+    async fn ingest_rejects_moving_a_recorded_instance_before_writing() {
+        let state = Arc::new(Mutex::new(State {
+            index_requests: vec![rustcoon_index::InstanceUpsertRequest::new(sample_record())],
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Updated,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        );
+        let mut request = sample_request();
+        request.record = DicomInstanceRecord::new(
+            DicomInstanceIdentity::new(
+                StudyInstanceUid::new("1.2.9").unwrap(),
+                SeriesInstanceUid::new("1.2.9.1").unwrap(),
+                SopInstanceUid::new("1.2.3.1.1").unwrap(),
+                SopClassUid::new("1.2.840.10008.5.1.4.1.1.2").unwrap(),
+            ),
+            DicomPatient::default(),
+            DicomStudyMetadata::default(),
+            DicomSeriesMetadata::default(),
+            rustcoon_dicom::DicomInstanceMetadata::default(),
+        );
+
+        let error = service
+            .ingest(request, &mut Cursor::new(b"moved".to_vec()))
+            .await
+            .expect_err("conflict");
+
+        assert!(matches!(
+            error,
+            crate::IngestError::CatalogUpdate {
+                source: IndexError::Conflict { .. },
+                rollback_failed: None,
+            }
+        ));
+        let state = state.lock().expect("state lock");
+        assert!(state.write_requests.is_empty());
+        assert_eq!(state.index_requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn keep_versions_writes_replacements_to_new_keys() {
+        let state = Arc::new(Mutex::new(State::default()));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Updated,
+            fail_upsert: false,
+        });
+        let index_read: Arc<dyn CatalogReadStore> = index_impl.clone();
+        let index_write: Arc<dyn CatalogWriteStore> = index_impl;
+        let service = IngestService::new(
+            storage,
+            index_read,
+            index_write,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_replacement_policy(ReplacementPolicy::KeepVersions);
+
+        let first = service
+            .ingest(sample_request(), &mut Cursor::new(b"first".to_vec()))
+            .await
+            .expect("first ingest");
+        let second = service
+            .ingest(sample_request(), &mut Cursor::new(b"second".to_vec()))
+            .await
+            .expect("second ingest");
+
+        assert_eq!(
+            first.blob.key.as_str(),
+            "instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm"
+        );
+        let versioned = second.blob.key.as_str();
+        assert!(versioned.starts_with("instances/1.2.3/1.2.3.1/1.2.3.1.1.v"));
+        assert!(versioned.ends_with(".dcm"));
+
+        let state = state.lock().expect("state lock");
+        assert_eq!(
+            state.blobs.get(first.blob.key.as_str()).expect("kept"),
+            b"first"
+        );
+        assert_eq!(state.blobs.get(versioned).expect("replacement"), b"second");
+        assert_eq!(
+            state.write_requests[1].precondition,
+            BlobWritePrecondition::MustNotExist
+        );
+        assert_eq!(
+            state.index_requests[1]
+                .blob
+                .as_ref()
+                .expect("blob ref")
+                .key
+                .as_str(),
+            versioned
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_only_reports_outcome_without_side_effects() {
+        let state = Arc::new(Mutex::new(State::default()));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let index_read: Arc<dyn CatalogReadStore> = index_impl.clone();
+        let index_write: Arc<dyn CatalogWriteStore> = index_impl;
+        let service = IngestService::new(
+            storage,
+            index_read,
+            index_write,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_chunk_size(4)
+        .with_max_instance_size_bytes(16);
+
+        let result = service
+            .ingest(
+                sample_request().with_validate_only(true),
+                &mut Cursor::new(b"dicom-payload".to_vec()),
+            )
+            .await
+            .expect("validate");
+        assert_eq!(result.outcome, IngestOutcome::Created);
+        assert_eq!(
+            result.blob.key.as_str(),
+            "instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm"
+        );
+        assert_eq!(result.blob.size_bytes, Some(13));
+
+        let oversized = service
+            .ingest(
+                sample_request().with_validate_only(true),
+                &mut Cursor::new(vec![0; 17]),
+            )
+            .await;
+        assert!(matches!(
+            oversized,
+            Err(crate::IngestError::InstanceTooLarge { max_size_bytes: 16 })
+        ));
+        {
+            let state = state.lock().expect("state lock");
+            assert!(state.blobs.is_empty());
+            assert!(state.write_requests.is_empty());
+            assert!(state.index_requests.is_empty());
+        }
+
+        service
+            .ingest(
+                sample_request(),
+                &mut Cursor::new(b"dicom-payload".to_vec()),
+            )
+            .await
+            .expect("ingest");
+        let service = service.with_validate_only(true);
+        let result = service
+            .ingest(sample_request(), &mut Cursor::new(b"replacement".to_vec()))
+            .await
+            .expect("validate replacement");
+        assert_eq!(result.outcome, IngestOutcome::Updated);
+
+        let state = state.lock().expect("state lock");
+        assert_eq!(state.write_requests.len(), 1);
+        assert_eq!(state.index_requests.len(), 1);
+        assert_eq!(
+            state
+                .blobs
+                .get("instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm")
+                .expect("stored payload"),
+            b"dicom-payload"
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_only_reports_the_catalog_preview_outcome() {
+        let state = Arc::new(Mutex::new(State::default()));
+        state
+            .lock()
+            .expect("state lock")
+            .index_requests
+            .push(rustcoon_index::InstanceUpsertRequest::new(sample_record()));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Unchanged,
+            fail_upsert: false,
+        });
+        let index_read: Arc<dyn CatalogReadStore> = index_impl.clone();
+        let index_write: Arc<dyn CatalogWriteStore> = index_impl;
+        let service = IngestService::new(
+            storage,
+            index_read,
+            index_write,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_validate_only(true);
+
+        let result = service
+            .ingest(
+                sample_request(),
+                &mut Cursor::new(b"dicom-payload".to_vec()),
+            )
+            .await
+            .expect("validate");
+
+        assert_eq!(result.outcome, IngestOutcome::Unchanged);
+        assert!(state.lock().expect("state lock").write_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn scan_payload_maps_scanner_verdicts() {
+        let state = Arc::new(Mutex::new(State::default()));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let index_read: Arc<dyn CatalogReadStore> = index_impl.clone();
+        let index_write: Arc<dyn CatalogWriteStore> = index_impl;
+        let service = || {
+            IngestService::new(
+                Arc::clone(&storage),
+                Arc::clone(&index_read),
+                Arc::clone(&index_write),
+                Arc::new(HierarchicalInstanceKeyResolver::new()),
+            )
+        };
+        let record = sample_record();
+        let payload = std::path::Path::new("payload.dcm");
+        let scan = |verdict| service().with_content_scanner(Arc::new(FixedScanner(verdict)));
+
+        service()
+            .scan_payload(&record, payload)
+            .await
+            .expect("no scanner configured");
+        scan(Ok(ScanVerdict::Clean))
+            .scan_payload(&record, payload)
+            .await
+            .expect("clean");
+        scan(Ok(ScanVerdict::Skipped))
+            .scan_payload(&record, payload)
+            .await
+            .expect("skipped");
+        assert!(matches!(
+            scan(Ok(ScanVerdict::Rejected {
+                reason: "EICAR".to_string()
+            }))
+            .scan_payload(&record, payload)
+            .await,
+            Err(crate::IngestError::ContentRejected { reason }) if reason == "EICAR"
+        ));
+        assert!(matches!(
+            scan(Err(std::io::ErrorKind::TimedOut))
+                .scan_payload(&record, payload)
+                .await,
+            Err(crate::IngestError::ContentScan(error))
+                if error.kind() == std::io::ErrorKind::TimedOut
+        ));
+    }
+
+    #[test]
+    fn versioned_key_inserts_version_before_extension() {
+        let key = BlobKey::new("instances/1.2/1.2.3.dcm").unwrap();
+        assert_eq!(
+            versioned_key(&key, 7).unwrap().as_str(),
+            "instances/1.2/1.2.3.v7.dcm"
+        );
+        let key = BlobKey::new("instances/.hidden").unwrap();
+        assert_eq!(
+            versioned_key(&key, 7).unwrap().as_str(),
+            "instances/.hidden.v7"
+        );
+    }
+
     #[tokio::test]
     async fn ingest_rolls_back_blob_when_catalog_update_fails() {
         let state = Arc::new(Mutex::new(State::default()));
@@ -619,6 +1419,253 @@ async fn ingest_rolls_back_blob_when_catalog_update_fails() {
         );
     }
 
+    #[tokio::test]
+    async fn ingest_rejects_a_racing_instance_when_the_catalog_reaches_the_limit() {
+        let state = Arc::new(Mutex::new(State {
+            study_counters: Some((9, 0)),
+            upsert_counters: Some((10, 0)),
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_study_limits(StudyLimits::new(StudyLimit {
+            max_instances: Some(10),
+            max_size_bytes: None,
+        }));
+
+        let error = service
+            .ingest(
+                sample_request(),
+                &mut Cursor::new(b"dicom-payload".to_vec()),
+            )
+            .await
+            .expect_err("study limit");
+
+        assert!(matches!(
+            error,
+            crate::IngestError::StudyLimitExceeded {
+                limit: "instance count",
+                current: 10,
+                max: 10,
+                ..
+            }
+        ));
+        let state = state.lock().expect("state lock");
+        assert!(state.blobs.is_empty());
+        assert_eq!(state.deleted.len(), 1);
+        assert!(state.index_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ingest_rejects_instances_once_study_limit_is_reached() {
+        let state = Arc::new(Mutex::new(State {
+            study_counters: Some((10, 2_048)),
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_study_limits(StudyLimits::new(StudyLimit {
+            max_instances: Some(10),
+            max_size_bytes: None,
+        }));
+
+        let mut payload = Cursor::new(b"dicom-payload".to_vec());
+        let error = service
+            .ingest(sample_request(), &mut payload)
+            .await
+            .expect_err("study limit");
+
+        match error {
+            crate::IngestError::StudyLimitExceeded {
+                study_instance_uid,
+                limit,
+                current,
+                max,
+            } => {
+                assert_eq!(study_instance_uid, "1.2.3");
+                assert_eq!(limit, "instance count");
+                assert_eq!((current, max), (10, 10));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let state = state.lock().expect("state lock");
+        assert!(state.write_requests.is_empty());
+        assert!(state.index_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ingest_replaces_existing_instances_in_a_full_study() {
+        let existing = rustcoon_index::InstanceUpsertRequest::new(sample_record()).with_blob(
+            StoredObjectRef::new(BlobKey::new("instances/1.2.3/1.2.3.1/1.2.3.1.1.dcm").unwrap())
+                .with_size_bytes(1_000),
+        );
+        let state = Arc::new(Mutex::new(State {
+            study_counters: Some((10, 2_000)),
+            index_requests: vec![existing],
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Updated,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_study_limits(StudyLimits::new(StudyLimit {
+            max_instances: Some(10),
+            max_size_bytes: Some(1_013),
+        }));
+
+        let mut payload = Cursor::new(b"dicom-payload".to_vec());
+        let result = service
+            .ingest(sample_request(), &mut payload)
+            .await
+            .expect("replacement fits the freed budget");
+
+        assert_eq!(result.outcome, IngestOutcome::Updated);
+    }
+
+    #[tokio::test]
+    async fn ingest_counts_incoming_payload_against_study_size_limit() {
+        let state = Arc::new(Mutex::new(State {
+            study_counters: Some((1, 1_000)),
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_study_limits(StudyLimits::new(StudyLimit {
+            max_instances: None,
+            max_size_bytes: Some(1_010),
+        }));
+
+        let mut payload = Cursor::new(b"dicom-payload".to_vec());
+        let error = service
+            .ingest(sample_request(), &mut payload)
+            .await
+            .expect_err("study size limit");
+
+        assert!(matches!(
+            error,
+            crate::IngestError::StudyLimitExceeded {
+                limit: "size",
+                current: 1_013,
+                max: 1_010,
+                ..
+            }
+        ));
+        let state = state.lock().expect("state lock");
+        assert!(state.blobs.is_empty());
+        assert!(state.index_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ingest_rejects_payloads_over_instance_size_limit() {
+        let state = Arc::new(Mutex::new(State::default()));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_chunk_size(4)
+        .with_max_instance_size_bytes(8);
+        assert_eq!(service.max_instance_size_bytes(), Some(8));
+
+        let mut payload = Cursor::new(b"dicom-payload".to_vec());
+        let error = service
+            .ingest(sample_request(), &mut payload)
+            .await
+            .expect_err("instance size limit");
+
+        assert!(matches!(
+            error,
+            crate::IngestError::InstanceTooLarge { max_size_bytes: 8 }
+        ));
+        let state = state.lock().expect("state lock");
+        assert!(state.blobs.is_empty());
+        assert!(state.index_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ingest_applies_study_limit_overrides() {
+        let state = Arc::new(Mutex::new(State {
+            study_counters: Some((10, 2_048)),
+            ..State::default()
+        }));
+        let storage: Arc<dyn BlobStore> = Arc::new(MockBlobStore::new(Arc::clone(&state)));
+        let index_impl = Arc::new(MockCatalog {
+            state: Arc::clone(&state),
+            outcome: CatalogUpsertOutcome::Created,
+            fail_upsert: false,
+        });
+        let service = IngestService::new(
+            storage,
+            index_impl.clone(),
+            index_impl,
+            Arc::new(HierarchicalInstanceKeyResolver::new()),
+        )
+        .with_study_limits(
+            StudyLimits::new(StudyLimit {
+                max_instances: Some(10),
+                max_size_bytes: Some(1_024),
+            })
+            .with_override(
+                StudyInstanceUid::new("1.2.3").unwrap(),
+                StudyLimit::default(),
+            ),
+        );
+
+        let mut payload = Cursor::new(b"dicom-payload".to_vec());
+        let result = service
+            .ingest(sample_request(), &mut payload)
+            .await
+            .expect("override lifts limit");
+
+        assert_eq!(result.outcome, IngestOutcome::Created);
+    }
+
     #[tokio::test]
     async fn ingest_propagates_begin_write_errors() {
         let state = Arc::new(Mutex::new(State::default()));
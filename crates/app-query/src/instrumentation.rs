@@ -42,6 +42,7 @@ pub(crate) fn find_span(request: &CFindRequest) -> Span {
         query.model = request.model.label(),
         query.level = field::Empty,
         match_count = field::Empty,
+        skipped_match_count = field::Empty,
     )
 }
 
@@ -57,6 +58,10 @@ pub(crate) fn record_match_count(match_count: usize) {
     Span::current().record("match_count", match_count as u64);
 }
 
+pub(crate) fn record_skipped_match_count(skipped_match_count: usize) {
+    Span::current().record("skipped_match_count", skipped_match_count as u64);
+}
+
 pub(crate) fn record_find_success(
     model: &'static str,
     level: &str,
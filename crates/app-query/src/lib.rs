@@ -9,5 +9,8 @@
 mod service;
 
 pub use error::QueryError;
-pub use model::{CFindMatch, CFindQueryModel, CFindRequest, CFindResponseLocation, CFindResult};
+pub use model::{
+    CFindMatch, CFindQueryModel, CFindRequest, CFindResponseLocation, CFindResult,
+    InvalidMatchHandling, StudyDateMatching,
+};
 pub use service::QueryService;
@@ -16,6 +16,28 @@ pub(crate) fn label(self) -> &'static str {
     }
 }
 
+/// Selects how Study Date keys are matched against the catalog.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StudyDateMatching {
+    /// Match the literal DA values as received from the sender.
+    #[default]
+    Literal,
+    /// Match Study Date, together with any Study Time key, against the UTC
+    /// study timestamp derived at ingest. Query values are read in the
+    /// identifier's Timezone Offset From UTC, or as UTC when it is absent.
+    NormalizedUtc,
+}
+
+/// Selects how catalog matches that cannot be shaped into a response are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvalidMatchHandling {
+    /// Log and omit the offending match so the remaining matches are returned.
+    #[default]
+    Skip,
+    /// Fail the whole C-FIND request.
+    FailFast,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CFindResponseLocation {
     RetrieveAeTitle(String),
@@ -4,10 +4,11 @@
 
 use dicom_core::dictionary::{DataDictionary, DataDictionaryEntry};
 use dicom_core::header::{DataElement, Header};
-use dicom_core::value::Value;
+use dicom_core::value::{PrimitiveValue, Value};
 use dicom_core::{Length, VR};
 use dicom_dictionary_std::{StandardDataDictionary, tags};
 use dicom_object::{InMemDicomObject, mem::InMemElement};
+use rustcoon_dicom::{normalize_date_time_utc, trim_value_padding};
 use rustcoon_index::{
     AttributePath, CatalogQuery, CatalogReadStore, ItemSelector, MatchingRule, Page, Predicate,
     QueryRetrieveScope, RangeMatching, SequenceMatching,
@@ -16,25 +17,71 @@
 
 use crate::error::QueryError;
 use crate::instrumentation;
-use crate::model::{CFindMatch, CFindQueryModel, CFindRequest, CFindResponseLocation, CFindResult};
+use crate::model::{
+    CFindMatch, CFindQueryModel, CFindRequest, CFindResponseLocation, CFindResult,
+    InvalidMatchHandling, StudyDateMatching,
+};
 
 pub struct QueryService {
     index: Arc<dyn CatalogReadStore>,
+    study_date_matching: StudyDateMatching,
+    invalid_match_handling: InvalidMatchHandling,
+    exclusion_matching: bool,
+    retrieve_ae_title: Option<String>,
 }
 
 impl QueryService {
     pub fn new(index: Arc<dyn CatalogReadStore>) -> Self {
-        Self { index }
+        Self {
+            index,
+            study_date_matching: StudyDateMatching::default(),
+            invalid_match_handling: InvalidMatchHandling::default(),
+            exclusion_matching: false,
+            retrieve_ae_title: None,
+        }
+    }
+
+    pub fn with_study_date_matching(mut self, study_date_matching: StudyDateMatching) -> Self {
+        self.study_date_matching = study_date_matching;
+        self
+    }
+
+    pub fn with_invalid_match_handling(
+        mut self,
+        invalid_match_handling: InvalidMatchHandling,
+    ) -> Self {
+        self.invalid_match_handling = invalid_match_handling;
+        self
+    }
+
+    /// Enables the non-standard `!value` exclusion syntax on single-valued,
+    /// non-UID keys. Off by default so standard values starting with `!`
+    /// keep their literal meaning.
+    pub fn with_exclusion_matching(mut self, exclusion_matching: bool) -> Self {
+        self.exclusion_matching = exclusion_matching;
+        self
     }
 
-    pub async fn find(&self, request: CFindRequest) -> Result<CFindResult, QueryError> {
+    /// Advertises a fixed Retrieve AE Title in every match instead of the
+    /// location supplied with the request, so archives behind a shared
+    /// front end report one consistent retrieve location.
+    pub fn with_retrieve_ae_title(mut self, retrieve_ae_title: impl Into<String>) -> Self {
+        self.retrieve_ae_title = Some(retrieve_ae_title.into());
+        self
+    }
+
+    pub async fn find(&self, mut request: CFindRequest) -> Result<CFindResult, QueryError> {
+        if let Some(ae_title) = &self.retrieve_ae_title {
+            request.response_location = CFindResponseLocation::RetrieveAeTitle(ae_title.clone());
+        }
         let span = instrumentation::find_span(&request);
         let started_at = Instant::now();
         let model = request.model.label();
         let mut observed_level = None;
 
         let result = async {
-            let built = build_catalog_query(&request)?;
+            let built =
+                build_catalog_query(&request, self.study_date_matching, self.exclusion_matching)?;
             instrumentation::record_query_level(&built.level);
             observed_level = Some(built.level.clone());
 
@@ -44,21 +91,36 @@ pub async fn find(&self, request: CFindRequest) -> Result<CFindResult, QueryErro
                 .instrument(instrumentation::catalog_query_span())
                 .await
                 .map_err(QueryError::Catalog)?;
-            let matches = page
-                .items
-                .into_iter()
-                .map(|entry| {
-                    response_identifier(
-                        entry.projection,
-                        &built.level,
-                        &request.response_location,
-                        &built.response_fields,
-                        built.specific_character_set.as_ref(),
-                    )
-                    .map(|identifier| CFindMatch { identifier })
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+            let mut matches = Vec::with_capacity(page.items.len());
+            let mut skipped = 0;
+            for entry in page.items {
+                match response_identifier(
+                    &entry.projection,
+                    &built.level,
+                    &request.response_location,
+                    &built.response_fields,
+                    built.specific_character_set.as_ref(),
+                ) {
+                    Ok(identifier) => matches.push(CFindMatch { identifier }),
+                    Err(error) if self.invalid_match_handling == InvalidMatchHandling::Skip => {
+                        skipped += 1;
+                        let study_instance_uid = entry
+                            .projection
+                            .element(tags::STUDY_INSTANCE_UID)
+                            .ok()
+                            .and_then(|element| element.to_str().ok())
+                            .map(|value| trim_value_padding(&value).to_string());
+                        tracing::warn!(
+                            study_instance_uid = study_instance_uid.as_deref().unwrap_or("unknown"),
+                            error = %error,
+                            "skipping C-FIND match that could not be shaped into a response"
+                        );
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
             instrumentation::record_match_count(matches.len());
+            instrumentation::record_skipped_match_count(skipped);
 
             Ok(CFindResult {
                 matches: Page {
@@ -114,7 +176,11 @@ enum ResponseField {
     },
 }
 
-fn build_catalog_query(request: &CFindRequest) -> Result<BuiltCatalogQuery, QueryError> {
+fn build_catalog_query(
+    request: &CFindRequest,
+    study_date_matching: StudyDateMatching,
+    exclusion_matching: bool,
+) -> Result<BuiltCatalogQuery, QueryError> {
     validate_response_location(&request.response_location)?;
     let level = query_retrieve_level(&request.identifier)?;
     let scope = scope_for(request.model, &level)?;
@@ -124,11 +190,19 @@ fn build_catalog_query(request: &CFindRequest) -> Result<BuiltCatalogQuery, Quer
     let mut response_fields = ResponseFields::new();
     add_required_response_keys(&mut response_fields, scope);
     let specific_character_set = requested_specific_character_set(&request.identifier)?;
+    let normalized_utc = study_date_matching == StudyDateMatching::NormalizedUtc;
+    let query_offset = if normalized_utc {
+        query_timezone_offset(&request.identifier)?
+    } else {
+        None
+    };
 
     let mut predicates = Vec::new();
+    let mut study_date = None;
+    let mut study_time = None;
     for element in request.identifier.iter() {
         let tag = element.tag();
-        if skip_control_attribute(element)? {
+        if skip_control_attribute(element, normalized_utc)? {
             continue;
         }
         validate_supported_query_key(request.model, tag)?;
@@ -137,10 +211,34 @@ fn build_catalog_query(request: &CFindRequest) -> Result<BuiltCatalogQuery, Quer
         let path = AttributePath::from_tag(tag);
         return_keys.insert(path.clone());
         response_fields.insert(response_field_for_request_element(element)?);
-        if let Some(predicate) = predicate_for_element(path, element)? {
-            predicates.push(predicate);
+        let predicate = if exclusion_matching {
+            exclusion_predicate(path.clone(), element)?
+        } else {
+            None
+        };
+        let predicate = match predicate {
+            Some(predicate) => Some(predicate),
+            None => predicate_for_element(path, element)?,
+        };
+        match predicate {
+            Some(predicate) if normalized_utc && tag == tags::STUDY_DATE => {
+                study_date = Some(predicate);
+            }
+            Some(predicate) if normalized_utc && tag == tags::STUDY_TIME => {
+                study_time = Some(predicate);
+            }
+            Some(predicate) => predicates.push(predicate),
+            None => {}
         }
     }
+    if let Some(date) = study_date {
+        match normalized_study_date_predicate(&date, study_time.as_ref(), query_offset.as_deref()) {
+            Some(predicate) => predicates.push(predicate),
+            None => predicates.extend(std::iter::once(date).chain(study_time)),
+        }
+    } else {
+        predicates.extend(study_time);
+    }
 
     let mut query = CatalogQuery::new(scope, return_keys.into_vec())
         .map_err(QueryError::InvalidCatalogQuery)?;
@@ -167,7 +265,7 @@ fn build_catalog_query(request: &CFindRequest) -> Result<BuiltCatalogQuery, Quer
 }
 
 fn response_identifier(
-    projection: InMemDicomObject,
+    projection: &InMemDicomObject,
     level: &str,
     location: &CFindResponseLocation,
     response_fields: &[ResponseField],
@@ -207,11 +305,32 @@ fn response_identifier(
 
     if let Some(element) = specific_character_set {
         identifier.put(element.clone());
+    } else if contains_non_ascii_text(&identifier) {
+        identifier.put(DataElement::new(
+            tags::SPECIFIC_CHARACTER_SET,
+            VR::CS,
+            UTF8_CHARACTER_SET,
+        ));
     }
 
     Ok(identifier)
 }
 
+/// Catalog text is decoded to UTF-8, so responses carrying non-ASCII values
+/// declare it explicitly when the requester did not name a character set.
+const UTF8_CHARACTER_SET: &str = "ISO_IR 192";
+
+fn contains_non_ascii_text(object: &InMemDicomObject) -> bool {
+    object.iter().any(|element| match element.value() {
+        Value::Primitive(PrimitiveValue::Str(value)) => !value.is_ascii(),
+        Value::Primitive(PrimitiveValue::Strs(values)) => {
+            values.iter().any(|value| !value.is_ascii())
+        }
+        Value::Sequence(sequence) => sequence.items().iter().any(contains_non_ascii_text),
+        _ => false,
+    })
+}
+
 fn zero_length_element(key: ResponseKey) -> InMemElement {
     if key.vr == VR::SQ {
         DataElement::new(
@@ -474,10 +593,14 @@ fn normalized_retrieve_ae_title(ae_title: &str) -> Result<String, QueryError> {
     Ok(normalized.to_string())
 }
 
-fn skip_control_attribute(element: &InMemElement) -> Result<bool, QueryError> {
+fn skip_control_attribute(
+    element: &InMemElement,
+    timezone_adjustment: bool,
+) -> Result<bool, QueryError> {
     let tag = element.tag();
     match tag {
         tags::QUERY_RETRIEVE_LEVEL | tags::SPECIFIC_CHARACTER_SET => Ok(true),
+        tags::TIMEZONE_OFFSET_FROM_UTC if timezone_adjustment => Ok(true),
         tags::TIMEZONE_OFFSET_FROM_UTC | tags::QUERY_RETRIEVE_VIEW => {
             if has_identifier_value(element)? {
                 return Err(QueryError::invalid_identifier_element(
@@ -775,17 +898,23 @@ fn predicate_for_element(
     let values = string_values(element)?;
     let values: Vec<String> = values
         .into_iter()
-        .map(|value| value.trim().to_string())
+        .map(|value| trim_value_padding(&value).to_string())
         .filter(|value| !value.is_empty())
         .collect();
 
     if values.is_empty() {
         return Ok(None);
     }
+    for value in &values {
+        validate_key_characters(element, value)?;
+    }
 
     let rule = if values.len() > 1 {
         if element.vr() == VR::UI {
             MatchingRule::UidList(values)
+        } else if element.tag() == tags::MODALITIES_IN_STUDY {
+            // A study matches when any of the requested modalities is present.
+            MatchingRule::MultipleValues(values)
         } else if StandardDataDictionary.by_tag(element.tag()).is_none() {
             return Ok(None);
         } else {
@@ -801,6 +930,145 @@ fn predicate_for_element(
     Ok(Some(Predicate::Attribute(path, rule)))
 }
 
+/// Builds a negated predicate for a single `!value` key, or `None` when the
+/// element does not use the exclusion syntax. Entities without the attribute
+/// do not carry the excluded value, so empty values match as well.
+fn exclusion_predicate(
+    path: AttributePath,
+    element: &InMemElement,
+) -> Result<Option<Predicate>, QueryError> {
+    if matches!(element.vr(), VR::SQ | VR::UI) {
+        return Ok(None);
+    }
+    let values = string_values(element)?;
+    let [value] = values.as_slice() else {
+        return Ok(None);
+    };
+    let Some(excluded) = value
+        .trim()
+        .strip_prefix('!')
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let excluded = DataElement::new(element.tag(), element.vr(), excluded);
+    Ok(
+        predicate_for_element(path.clone(), &excluded)?.map(|predicate| {
+            Predicate::Any(vec![
+                Predicate::Attribute(path, MatchingRule::EmptyValue),
+                Predicate::Not(Box::new(predicate)),
+            ])
+        }),
+    )
+}
+
+/// Timezone Offset From UTC the identifier's date and time keys are expressed in.
+fn query_timezone_offset(identifier: &InMemDicomObject) -> Result<Option<String>, QueryError> {
+    let Ok(element) = identifier.element(tags::TIMEZONE_OFFSET_FROM_UTC) else {
+        return Ok(None);
+    };
+    let offset = string_values(element)?
+        .first()
+        .map(|value| trim_value_padding(value).to_string())
+        .filter(|value| !value.is_empty());
+    if offset
+        .as_deref()
+        .is_some_and(|offset| !valid_timezone(offset))
+    {
+        return Err(QueryError::invalid_identifier_element(
+            tags::TIMEZONE_OFFSET_FROM_UTC,
+            "invalid timezone offset",
+        ));
+    }
+    Ok(offset)
+}
+
+/// Rewrites Study Date, combined with any Study Time key, into a range over
+/// the UTC study timestamp. Returns `None` when the keys cannot be combined,
+/// in which case they are matched literally.
+fn normalized_study_date_predicate(
+    date: &Predicate,
+    time: Option<&Predicate>,
+    offset: Option<&str>,
+) -> Option<Predicate> {
+    let (date, excluded) = match date {
+        Predicate::Any(branches) => match branches.as_slice() {
+            [
+                Predicate::Attribute(_, MatchingRule::EmptyValue),
+                Predicate::Not(inner),
+            ] => (inner.as_ref(), true),
+            _ => return None,
+        },
+        date => (date, false),
+    };
+    if excluded && time.is_some() {
+        return None;
+    }
+
+    let (date_start, date_end) = predicate_bounds(date)?;
+    let (time_start, time_end) = match time {
+        Some(time) => predicate_bounds(time)?,
+        None => (None, None),
+    };
+    let start = match date_start {
+        Some(date) => Some(normalize_date_time_utc(
+            &date,
+            Some(&time_floor(time_start.as_deref())?),
+            offset,
+        )?),
+        None => None,
+    };
+    let end = match date_end {
+        Some(date) => Some(normalize_date_time_utc(
+            &date,
+            Some(&time_ceiling(time_end.as_deref())?),
+            offset,
+        )?),
+        None => None,
+    };
+
+    let predicate = Predicate::StudyDateTimeUtc(RangeMatching { start, end });
+    Some(if excluded {
+        Predicate::Any(vec![
+            Predicate::Attribute(
+                AttributePath::from_tag(tags::STUDY_DATE),
+                MatchingRule::EmptyValue,
+            ),
+            Predicate::Not(Box::new(predicate)),
+        ])
+    } else {
+        predicate
+    })
+}
+
+fn predicate_bounds(predicate: &Predicate) -> Option<(Option<String>, Option<String>)> {
+    match predicate {
+        Predicate::Attribute(_, MatchingRule::SingleValue(value)) => {
+            Some((Some(value.clone()), Some(value.clone())))
+        }
+        Predicate::Attribute(_, MatchingRule::Range(range)) => {
+            Some((range.start.clone(), range.end.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn time_floor(time: Option<&str>) -> Option<String> {
+    let whole = split_fraction(time.unwrap_or("00")).0;
+    matches!(whole.len(), 2 | 4 | 6).then(|| format!("{whole:0<6}"))
+}
+
+fn time_ceiling(time: Option<&str>) -> Option<String> {
+    let whole = split_fraction(time.unwrap_or("23")).0;
+    match whole.len() {
+        2 => Some(format!("{whole}5959")),
+        4 => Some(format!("{whole}59")),
+        6 => Some(whole.to_string()),
+        _ => None,
+    }
+}
+
 fn sequence_predicate(
     path: AttributePath,
     element: &InMemElement,
@@ -870,7 +1138,7 @@ fn string_values(element: &InMemElement) -> Result<Vec<String>, QueryError> {
 fn non_empty_string_values(element: &InMemElement) -> Result<Vec<String>, QueryError> {
     Ok(string_values(element)?
         .into_iter()
-        .map(|value| value.trim().to_string())
+        .map(|value| trim_value_padding(&value).to_string())
         .filter(|value| !value.is_empty())
         .collect())
 }
@@ -883,6 +1151,35 @@ fn has_identifier_value(element: &InMemElement) -> Result<bool, QueryError> {
     Ok(!non_empty_string_values(element)?.is_empty())
 }
 
+/// Rejects key values that could not have come from a valid encoding: bytes
+/// the character set could not decode, stray control characters, or
+/// characters outside the UID repertoire.
+fn validate_key_characters(element: &InMemElement, value: &str) -> Result<(), QueryError> {
+    let allows_text_controls = matches!(element.vr(), VR::LT | VR::ST | VR::UT);
+    let invalid = value.chars().find(|ch| {
+        *ch == char::REPLACEMENT_CHARACTER
+            || (ch.is_control()
+                && !(allows_text_controls && matches!(ch, '\t' | '\n' | '\r' | '\x0c')))
+    });
+    if let Some(ch) = invalid {
+        return Err(QueryError::invalid_identifier_element(
+            element.tag(),
+            format!("key value contains invalid character {ch:?}"),
+        ));
+    }
+    if element.vr() == VR::UI
+        && !value
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || matches!(ch, '.' | '*' | '?'))
+    {
+        return Err(QueryError::invalid_identifier_element(
+            element.tag(),
+            "UID key value may contain only digits and periods",
+        ));
+    }
+    Ok(())
+}
+
 fn matching_rule_for_single_value(
     element: &InMemElement,
     value: String,
@@ -1249,13 +1546,17 @@ mod tests {
     };
 
     use super::build_catalog_query;
-    use crate::{CFindQueryModel, CFindRequest, CFindResponseLocation, QueryError, QueryService};
+    use crate::{
+        CFindQueryModel, CFindRequest, CFindResponseLocation, InvalidMatchHandling, QueryError,
+        QueryService, StudyDateMatching,
+    };
 
     #[derive(Default)]
     struct MockCatalogReadStore {
         query: Mutex<Option<CatalogQuery>>,
         fail_query: bool,
         projection: Mutex<Option<InMemDicomObject>>,
+        extra_projections: Vec<InMemDicomObject>,
     }
 
     #[async_trait]
@@ -1299,12 +1600,24 @@ async fn query(&self, query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, In
             if projection.element(tags::STUDY_INSTANCE_UID).is_err() {
                 projection.put(DataElement::new(tags::STUDY_INSTANCE_UID, VR::UI, "1.2.3"));
             }
+            let entries = std::iter::once(projection)
+                .chain(self.extra_projections.iter().cloned())
+                .map(|projection| CatalogQueryEntry { projection })
+                .collect::<Vec<_>>();
+            let total = entries.len();
             Ok(Page::new(
-                vec![CatalogQueryEntry { projection }],
+                entries,
                 Some(Paging::new(20, 10).expect("valid paging")),
-                Some(1),
+                Some(total),
             ))
         }
+
+        async fn list_superseded_blobs(
+            &self,
+            _sop_instance_uid: &rustcoon_dicom::SopInstanceUid,
+        ) -> Result<Vec<rustcoon_index::StoredObjectRef>, IndexError> {
+            Ok(Vec::new())
+        }
     }
 
     fn request(model: CFindQueryModel, identifier: InMemDicomObject) -> CFindRequest {
@@ -1321,7 +1634,7 @@ fn relational_request(model: CFindQueryModel, identifier: InMemDicomObject) -> C
     }
 
     fn catalog_query(request: &CFindRequest) -> Result<CatalogQuery, QueryError> {
-        build_catalog_query(request).map(|built| built.query)
+        build_catalog_query(request, StudyDateMatching::Literal, false).map(|built| built.query)
     }
 
     fn identifier(level: &str) -> InMemDicomObject {
@@ -1489,19 +1802,19 @@ fn patient_root_levels_map_to_catalog_scopes() {
 
     #[test]
     fn rejects_missing_invalid_and_unsupported_levels() {
-        let missing = build_catalog_query(&request(
+        let missing = catalog_query(&request(
             CFindQueryModel::StudyRoot,
             InMemDicomObject::new_empty(),
         ))
         .expect_err("missing level");
         assert!(matches!(missing, QueryError::MissingQueryRetrieveLevel));
 
-        let empty = build_catalog_query(&request(CFindQueryModel::StudyRoot, identifier("")))
+        let empty = catalog_query(&request(CFindQueryModel::StudyRoot, identifier("")))
             .expect_err("empty level");
         assert!(matches!(empty, QueryError::MissingQueryRetrieveLevel));
 
         let unsupported =
-            build_catalog_query(&request(CFindQueryModel::StudyRoot, identifier("PATIENT")))
+            catalog_query(&request(CFindQueryModel::StudyRoot, identifier("PATIENT")))
                 .expect_err("patient level unsupported in study root");
         assert!(matches!(
             unsupported,
@@ -1802,6 +2115,22 @@ fn rejects_non_uid_multiple_value_matching_without_extended_negotiation() {
         ));
     }
 
+    #[test]
+    fn modalities_in_study_accepts_multiple_values() {
+        let object = with_multi(
+            identifier("STUDY"),
+            tags::MODALITIES_IN_STUDY,
+            VR::CS,
+            vec!["CT", "MR"],
+        );
+
+        let query = catalog_query(&request(CFindQueryModel::StudyRoot, object)).expect("query");
+        assert!(matches!(
+            predicate_for_tag(&query, tags::MODALITIES_IN_STUDY),
+            MatchingRule::MultipleValues(values) if values == &vec!["CT".to_string(), "MR".to_string()]
+        ));
+    }
+
     #[test]
     fn rejects_wildcard_matching_for_unsupported_vrs() {
         let object = with_str(identifier("IMAGE"), tags::SOP_CLASS_UID, VR::UI, "1.2.?");
@@ -1818,6 +2147,33 @@ fn rejects_wildcard_matching_for_unsupported_vrs() {
         ));
     }
 
+    #[test]
+    fn rejects_undecodable_and_out_of_repertoire_key_values() {
+        for (tag, vr, value) in [
+            (tags::PATIENT_ID, VR::LO, "PAT\u{FFFD}1"),
+            (tags::PATIENT_NAME, VR::PN, "DOE\u{1}"),
+            (tags::SERIES_INSTANCE_UID, VR::UI, "1.2.3a"),
+        ] {
+            let object = with_str(identifier("SERIES"), tag, vr, value);
+
+            let error = catalog_query(&relational_request(CFindQueryModel::StudyRoot, object))
+                .expect_err("invalid key value");
+
+            assert!(
+                matches!(error, QueryError::InvalidIdentifierElement { tag: rejected, .. } if rejected == tag),
+                "{value:?} was not rejected: {error:?}"
+            );
+        }
+
+        let object = with_str(
+            identifier("IMAGE"),
+            tags::IMAGE_COMMENTS,
+            VR::LT,
+            "line one\r\nline two",
+        );
+        assert!(catalog_query(&relational_request(CFindQueryModel::StudyRoot, object)).is_ok());
+    }
+
     #[test]
     fn rejects_asterisk_universal_for_unsupported_vrs() {
         let object = with_str(identifier("IMAGE"), tags::SOP_CLASS_UID, VR::UI, "*");
@@ -1876,6 +2232,184 @@ fn builds_universal_open_range_and_question_wildcard_predicates() {
         ));
     }
 
+    #[test]
+    fn normalized_study_date_matching_targets_utc_study_timestamp() {
+        let object = with_str(
+            identifier("STUDY"),
+            tags::STUDY_DATE,
+            VR::DA,
+            "20260101-20260131",
+        );
+        let object = with_str(object, tags::PATIENT_ID, VR::LO, "PAT-001");
+        let find = request(CFindQueryModel::StudyRoot, object);
+
+        let query = build_catalog_query(&find, StudyDateMatching::NormalizedUtc, false)
+            .expect("query")
+            .query;
+
+        assert!(all_predicates(&query).iter().any(|predicate| matches!(
+            predicate,
+            Predicate::StudyDateTimeUtc(range)
+                if range == &RangeMatching::closed("20260101000000", "20260131235959")
+        )));
+        assert!(has_return_key(&query, tags::STUDY_DATE));
+        assert!(matches!(
+            predicate_for_tag(&query, tags::PATIENT_ID),
+            MatchingRule::SingleValue(value) if value == "PAT-001"
+        ));
+
+        let single = with_str(identifier("STUDY"), tags::STUDY_DATE, VR::DA, "20260411");
+        let query = build_catalog_query(
+            &request(CFindQueryModel::StudyRoot, single),
+            StudyDateMatching::NormalizedUtc,
+            false,
+        )
+        .expect("query")
+        .query;
+        assert!(matches!(
+            query.predicate(),
+            Some(Predicate::StudyDateTimeUtc(range))
+                if range == &RangeMatching::closed("20260411000000", "20260411235959")
+        ));
+    }
+
+    #[test]
+    fn normalized_study_date_matching_combines_time_and_query_offset() {
+        let object = with_str(
+            identifier("STUDY"),
+            tags::STUDY_DATE,
+            VR::DA,
+            "20260101-20260131",
+        );
+        let object = with_str(object, tags::STUDY_TIME, VR::TM, "0800-1730");
+        let object = with_str(object, tags::TIMEZONE_OFFSET_FROM_UTC, VR::SH, "+0800");
+        let query = build_catalog_query(
+            &request(CFindQueryModel::StudyRoot, object),
+            StudyDateMatching::NormalizedUtc,
+            false,
+        )
+        .expect("query")
+        .query;
+        assert!(matches!(
+            query.predicate(),
+            Some(Predicate::StudyDateTimeUtc(range))
+                if range == &RangeMatching::closed("20260101000000", "20260131093059")
+        ));
+        assert!(has_return_key(&query, tags::STUDY_TIME));
+
+        let object = with_str(identifier("STUDY"), tags::STUDY_DATE, VR::DA, "!20260411");
+        let query = build_catalog_query(
+            &request(CFindQueryModel::StudyRoot, object),
+            StudyDateMatching::NormalizedUtc,
+            true,
+        )
+        .expect("query")
+        .query;
+        let Some(Predicate::Any(branches)) = query.predicate() else {
+            panic!("expected excluded study date");
+        };
+        assert!(matches!(
+            branches.as_slice(),
+            [
+                Predicate::Attribute(_, MatchingRule::EmptyValue),
+                Predicate::Not(inner),
+            ] if matches!(
+                inner.as_ref(),
+                Predicate::StudyDateTimeUtc(range)
+                    if range == &RangeMatching::closed("20260411000000", "20260411235959")
+            )
+        ));
+
+        let object = with_str(identifier("STUDY"), tags::STUDY_DATE, VR::DA, "!20260411");
+        let object = with_str(object, tags::STUDY_TIME, VR::TM, "0800-1730");
+        let query = build_catalog_query(
+            &request(CFindQueryModel::StudyRoot, object),
+            StudyDateMatching::NormalizedUtc,
+            true,
+        )
+        .expect("query")
+        .query;
+        assert!(
+            all_predicates(&query)
+                .iter()
+                .all(|predicate| !matches!(predicate, Predicate::StudyDateTimeUtc(_)))
+        );
+        assert!(matches!(
+            predicate_for_tag(&query, tags::STUDY_TIME),
+            MatchingRule::Range(_)
+        ));
+
+        let object = with_str(identifier("STUDY"), tags::STUDY_DATE, VR::DA, "20260411");
+        let object = with_str(object, tags::TIMEZONE_OFFSET_FROM_UTC, VR::SH, "+2500");
+        assert!(matches!(
+            build_catalog_query(
+                &request(CFindQueryModel::StudyRoot, object),
+                StudyDateMatching::NormalizedUtc,
+                false,
+            ),
+            Err(QueryError::InvalidIdentifierElement {
+                tag: tags::TIMEZONE_OFFSET_FROM_UTC,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn padded_query_values_match_trimmed_catalog_values() {
+        let object = with_str(
+            identifier("STUDY"),
+            tags::STUDY_INSTANCE_UID,
+            VR::UI,
+            "1.2.3\0",
+        );
+        let object = with_str(object, tags::PATIENT_ID, VR::LO, " PAT-001 ");
+        let query = catalog_query(&request(CFindQueryModel::StudyRoot, object)).expect("query");
+
+        assert!(matches!(
+            predicate_for_tag(&query, tags::STUDY_INSTANCE_UID),
+            MatchingRule::SingleValue(value) if value == "1.2.3"
+        ));
+        assert!(matches!(
+            predicate_for_tag(&query, tags::PATIENT_ID),
+            MatchingRule::SingleValue(value) if value == "PAT-001"
+        ));
+    }
+
+    #[test]
+    fn exclusion_matching_negates_prefixed_values_only_when_enabled() {
+        let object = with_str(identifier("SERIES"), tags::MODALITY, VR::CS, "!SR");
+        let object = with_str(object, tags::STUDY_INSTANCE_UID, VR::UI, "1.2.3");
+        let find = request(CFindQueryModel::StudyRoot, object);
+
+        let query = build_catalog_query(&find, StudyDateMatching::Literal, true)
+            .expect("query")
+            .query;
+        let modality = AttributePath::from_tag(tags::MODALITY);
+        assert!(all_predicates(&query).iter().any(|predicate| matches!(
+            predicate,
+            Predicate::Any(alternatives) if matches!(
+                alternatives.as_slice(),
+                [
+                    Predicate::Attribute(empty_path, MatchingRule::EmptyValue),
+                    Predicate::Not(inner),
+                ] if empty_path == &modality && matches!(
+                    inner.as_ref(),
+                    Predicate::Attribute(path, MatchingRule::SingleValue(value))
+                        if path == &modality && value == "SR"
+                )
+            )
+        )));
+        assert!(has_return_key(&query, tags::MODALITY));
+
+        let query = build_catalog_query(&find, StudyDateMatching::Literal, false)
+            .expect("query")
+            .query;
+        assert!(matches!(
+            predicate_for_tag(&query, tags::MODALITY),
+            MatchingRule::SingleValue(value) if value == "!SR"
+        ));
+    }
+
     #[test]
     fn builds_sequence_predicates_using_any_item_selector() {
         let mut item = InMemDicomObject::new_empty();
@@ -2229,6 +2763,27 @@ async fn service_returns_projected_identifiers_and_preserves_page_summary() {
         assert!(store.query.lock().expect("query lock").is_some());
     }
 
+    #[tokio::test]
+    async fn configured_retrieve_ae_title_overrides_request_location() {
+        let store = Arc::new(MockCatalogReadStore::default());
+        let service = QueryService::new(store).with_retrieve_ae_title("ARCHIVE_GW");
+
+        let result = service
+            .find(request(CFindQueryModel::StudyRoot, identifier("STUDY")))
+            .await
+            .expect("find");
+
+        assert_eq!(
+            result.matches.items[0]
+                .identifier
+                .element(tags::RETRIEVE_AE_TITLE)
+                .expect("retrieve ae title")
+                .to_str()
+                .expect("string"),
+            "ARCHIVE_GW"
+        );
+    }
+
     #[tokio::test]
     async fn service_inserts_zero_length_requested_keys_missing_from_projection() {
         let store = Arc::new(MockCatalogReadStore::default());
@@ -2338,6 +2893,117 @@ async fn service_propagates_specific_character_set_to_response() {
         assert_eq!(charset.to_str().expect("string"), "ISO_IR 192");
     }
 
+    #[tokio::test]
+    async fn service_declares_utf8_for_non_ascii_responses_without_requested_charset() {
+        let store = Arc::new(MockCatalogReadStore {
+            projection: Mutex::new(Some(with_str(
+                InMemDicomObject::new_empty(),
+                tags::PATIENT_NAME,
+                VR::PN,
+                "Müller^Jürgen",
+            ))),
+            ..Default::default()
+        });
+        let service = QueryService::new(store);
+        let object = with_str(identifier("STUDY"), tags::PATIENT_NAME, VR::PN, "");
+
+        let result = service
+            .find(request(CFindQueryModel::StudyRoot, object))
+            .await
+            .expect("find");
+
+        let charset = result.matches.items[0]
+            .identifier
+            .element(tags::SPECIFIC_CHARACTER_SET)
+            .expect("specific character set");
+        assert_eq!(charset.to_str().expect("string"), "ISO_IR 192");
+    }
+
+    fn sequence_request_and_projections() -> (InMemDicomObject, Vec<InMemDicomObject>) {
+        let mut request_item = InMemDicomObject::new_empty();
+        request_item.put(DataElement::new(
+            tags::SCHEDULED_PROCEDURE_STEP_ID,
+            VR::SH,
+            "",
+        ));
+        let object = with_sequence(
+            identifier("IMAGE"),
+            tags::REQUEST_ATTRIBUTES_SEQUENCE,
+            vec![request_item],
+        );
+
+        let projections = ["1.2.3.1", "1.2.3.2", "1.2.3.3"]
+            .into_iter()
+            .map(|sop_instance_uid| {
+                let mut projection = InMemDicomObject::new_empty();
+                projection.put(DataElement::new(
+                    tags::SOP_INSTANCE_UID,
+                    VR::UI,
+                    sop_instance_uid,
+                ));
+                if sop_instance_uid == "1.2.3.2" {
+                    projection.put(DataElement::new(
+                        tags::REQUEST_ATTRIBUTES_SEQUENCE,
+                        VR::SH,
+                        "not-a-sequence",
+                    ));
+                }
+                projection
+            })
+            .collect();
+        (object, projections)
+    }
+
+    #[tokio::test]
+    async fn service_skips_matches_that_cannot_be_shaped() {
+        let (object, mut projections) = sequence_request_and_projections();
+        let store = Arc::new(MockCatalogReadStore {
+            projection: Mutex::new(Some(projections.remove(0))),
+            extra_projections: projections,
+            ..Default::default()
+        });
+        let service = QueryService::new(store);
+
+        let result = service
+            .find(relational_request(CFindQueryModel::StudyRoot, object))
+            .await
+            .expect("find");
+
+        let sop_instance_uids = result
+            .matches
+            .items
+            .iter()
+            .map(|item| {
+                item.identifier
+                    .element(tags::SOP_INSTANCE_UID)
+                    .expect("sop instance uid")
+                    .to_str()
+                    .expect("string")
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sop_instance_uids, vec!["1.2.3.1", "1.2.3.3"]);
+    }
+
+    #[tokio::test]
+    async fn service_fails_fast_on_unshapeable_match_when_configured() {
+        let (object, mut projections) = sequence_request_and_projections();
+        let store = Arc::new(MockCatalogReadStore {
+            projection: Mutex::new(Some(projections.remove(0))),
+            extra_projections: projections,
+            ..Default::default()
+        });
+        let service =
+            QueryService::new(store).with_invalid_match_handling(InvalidMatchHandling::FailFast);
+
+        let error = service
+            .find(relational_request(CFindQueryModel::StudyRoot, object))
+            .await
+            .expect_err("unshapeable match");
+
+        assert!(matches!(error, QueryError::InvalidIdentifierElement { .. }));
+    }
+
     #[tokio::test]
     async fn service_maps_catalog_errors() {
         let store = Arc::new(MockCatalogReadStore {
@@ -498,6 +498,13 @@ async fn query(&self, _query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, I
 
             Ok(Page::new(items, None, Some(state.query_instances.len())))
         }
+
+        async fn list_superseded_blobs(
+            &self,
+            _sop_instance_uid: &SopInstanceUid,
+        ) -> Result<Vec<StoredObjectRef>, IndexError> {
+            Ok(Vec::new())
+        }
     }
 
     struct MockStorage {
@@ -0,0 +1,176 @@
+/// Combines DICOM DA, TM, and Timezone Offset From UTC values into a UTC timestamp.
+///
+/// The result is formatted as `YYYYMMDDHHMMSS` so it sorts lexically in time
+/// order. Missing time components default to zero, fractional seconds are
+/// dropped, and a missing offset is treated as UTC. Returns `None` when any
+/// supplied value is malformed.
+pub fn normalize_date_time_utc(
+    date: &str,
+    time: Option<&str>,
+    offset: Option<&str>,
+) -> Option<String> {
+    let (year, month, day) = parse_date(date.trim())?;
+    let (hour, minute, second) = match time.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(time) => parse_time(time)?,
+        None => (0, 0, 0),
+    };
+    let offset_minutes = match offset.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(offset) => parse_offset_minutes(offset)?,
+        None => 0,
+    };
+
+    let local_minutes = days_from_civil(year, month, day) * 1_440 + i64::from(hour * 60 + minute);
+    let utc_minutes = local_minutes - offset_minutes;
+    let (year, month, day) = civil_from_days(utc_minutes.div_euclid(1_440));
+    let minute_of_day = utc_minutes.rem_euclid(1_440);
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    Some(format!(
+        "{year:04}{month:02}{day:02}{:02}{:02}{second:02}",
+        minute_of_day / 60,
+        minute_of_day % 60,
+    ))
+}
+
+fn parse_date(value: &str) -> Option<(i64, u32, u32)> {
+    if value.len() != 8 || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let year = value[0..4].parse::<i64>().ok()?;
+    let month = value[4..6].parse::<u32>().ok()?;
+    let day = value[6..8].parse::<u32>().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+fn parse_time(value: &str) -> Option<(u32, u32, u32)> {
+    let whole = value.split_once('.').map_or(value, |(whole, _)| whole);
+    if !matches!(whole.len(), 2 | 4 | 6) || !whole.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let component = |range: std::ops::Range<usize>| {
+        whole
+            .get(range)
+            .map_or(Some(0), |part| part.parse::<u32>().ok())
+    };
+    let hour = component(0..2)?;
+    let minute = component(2..4)?;
+    let second = component(4..6)?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    Some((hour, minute, second.min(59)))
+}
+
+fn parse_offset_minutes(value: &str) -> Option<i64> {
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let hours = digits[0..2].parse::<i64>().ok()?;
+    let minutes = digits[2..4].parse::<i64>().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes))
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = (if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_date_time_utc;
+
+    #[test]
+    fn applies_timezone_offset_across_day_boundaries() {
+        assert_eq!(
+            normalize_date_time_utc("20260411", Some("233000"), Some("-0500")).as_deref(),
+            Some("20260412043000")
+        );
+        assert_eq!(
+            normalize_date_time_utc("20260101", Some("0100"), Some("+0900")).as_deref(),
+            Some("20251231160000")
+        );
+        assert_eq!(
+            normalize_date_time_utc("20240229", Some("12"), Some("+0000")).as_deref(),
+            Some("20240229120000")
+        );
+    }
+
+    #[test]
+    fn defaults_missing_time_and_offset() {
+        assert_eq!(
+            normalize_date_time_utc("20260411", None, None).as_deref(),
+            Some("20260411000000")
+        );
+        assert_eq!(
+            normalize_date_time_utc(" 20260411 ", Some("101530.123456 "), Some(" ")).as_deref(),
+            Some("20260411101530")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_values() {
+        assert_eq!(normalize_date_time_utc("2026041", None, None), None);
+        assert_eq!(normalize_date_time_utc("20260230", None, None), None);
+        assert_eq!(
+            normalize_date_time_utc("20260411", Some("2460"), None),
+            None
+        );
+        assert_eq!(
+            normalize_date_time_utc("20260411", Some("10:15"), None),
+            None
+        );
+        assert_eq!(
+            normalize_date_time_utc("20260411", None, Some("0500")),
+            None
+        );
+        assert_eq!(
+            normalize_date_time_utc("20260411", None, Some("+1500")),
+            None
+        );
+    }
+}
@@ -5,16 +5,20 @@
 //! UID identity, hierarchy, normalized metadata, and invariants around those
 //! records.
 
+mod datetime;
 mod error;
 mod identity;
 mod metadata;
 mod record;
+mod text;
 mod uid;
 
+pub use datetime::normalize_date_time_utc;
 pub use error::DicomUidError;
 pub use identity::{DicomInstanceIdentity, DicomSeriesIdentity, DicomStudyIdentity};
 pub use metadata::{DicomInstanceMetadata, DicomPatient, DicomSeriesMetadata, DicomStudyMetadata};
 pub use record::{DicomInstanceRecord, DicomSeriesRecord, DicomStudyRecord};
+pub use text::{fold_person_name, trim_trailing_padding, trim_value_padding};
 pub use uid::{
     SeriesInstanceUid, SopClassUid, SopInstanceUid, StudyInstanceUid, TransferSyntaxUid,
 };
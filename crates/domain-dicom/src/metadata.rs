@@ -1,8 +1,8 @@
-use crate::TransferSyntaxUid;
+use crate::{TransferSyntaxUid, trim_value_padding};
 
 fn normalize_optional(value: Option<String>) -> Option<String> {
     value.and_then(|value| {
-        let trimmed = value.trim();
+        let trimmed = trim_value_padding(&value);
         if trimmed.is_empty() {
             None
         } else {
@@ -0,0 +1,79 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Strips DICOM value padding: leading/trailing spaces and NUL bytes.
+///
+/// Suitable for VRs where surrounding spaces are insignificant (UI, CS, SH,
+/// LO, PN, DA, TM, ...). Text VRs such as ST, LT and UT keep leading spaces.
+pub fn trim_value_padding(value: &str) -> &str {
+    value.trim_matches(|character: char| character == '\0' || character.is_whitespace())
+}
+
+/// Strips trailing DICOM value padding only.
+pub fn trim_trailing_padding(value: &str) -> &str {
+    value.trim_end_matches(|character: char| character == '\0' || character.is_whitespace())
+}
+
+/// Folds a person name for matching and ordering: padding is trimmed,
+/// accents are removed and letters are uppercased, so "Müller" and "MULLER"
+/// compare equal regardless of database collation. DICOM wildcards pass
+/// through unchanged.
+pub fn fold_person_name(value: &str) -> String {
+    let mut folded = String::with_capacity(value.len());
+    for character in trim_value_padding(value)
+        .nfd()
+        .filter(|character| !is_combining_mark(*character))
+    {
+        match fold_letter(character) {
+            Some(letters) => folded.push_str(letters),
+            None => folded.extend(character.to_uppercase()),
+        }
+    }
+    folded
+}
+
+/// Latin letters written with a stroke or as ligatures. They have no
+/// canonical decomposition, so stripping combining marks leaves them intact.
+fn fold_letter(character: char) -> Option<&'static str> {
+    Some(match character {
+        'Ø' | 'ø' => "O",
+        'Ł' | 'ł' => "L",
+        'Đ' | 'đ' | 'Ð' | 'ð' => "D",
+        'Ħ' | 'ħ' => "H",
+        'Ŧ' | 'ŧ' => "T",
+        'Æ' | 'æ' => "AE",
+        'Œ' | 'œ' => "OE",
+        'Þ' | 'þ' => "TH",
+        'ß' | 'ẞ' => "SS",
+        'ı' => "I",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fold_person_name, trim_trailing_padding, trim_value_padding};
+
+    #[test]
+    fn trims_spaces_and_null_padding() {
+        assert_eq!(trim_value_padding(" 1.2.3\0"), "1.2.3");
+        assert_eq!(trim_value_padding("CT "), "CT");
+        assert_eq!(trim_value_padding(" \0"), "");
+        assert_eq!(
+            trim_trailing_padding("  indented text \0"),
+            "  indented text"
+        );
+    }
+
+    #[test]
+    fn folds_person_names_to_unaccented_uppercase() {
+        assert_eq!(fold_person_name("Müller^Hans "), "MULLER^HANS");
+        assert_eq!(fold_person_name("MULLER*"), "MULLER*");
+        assert_eq!(fold_person_name("Ångström^Zoë"), "ANGSTROM^ZOE");
+        assert_eq!(fold_person_name("o\u{0301}scar"), "OSCAR");
+        assert_eq!(fold_person_name("José?"), "JOSE?");
+        assert_eq!(fold_person_name("Ødegård^Ola"), "ODEGARD^OLA");
+        assert_eq!(fold_person_name("Łukasz^Đorđe"), "LUKASZ^DORDE");
+        assert_eq!(fold_person_name("Æsir^Strauß"), "AESIR^STRAUSS");
+    }
+}
@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Ingest service behaviour configuration.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct IngestConfig {
+    /// Maximum number of instances accepted per study.
+    pub max_instances_per_study: Option<u64>,
+
+    /// Maximum total stored bytes accepted per study.
+    pub max_study_size_bytes: Option<u64>,
+
+    /// Per-study limits that replace the defaults above.
+    pub study_limit_overrides: Vec<StudyLimitOverrideConfig>,
+
+    /// Maximum size of a single received instance, checked independently of
+    /// the study totals. Must be at least 1 MiB; omit for no limit.
+    pub max_instance_size_bytes: Option<u64>,
+
+    /// Handling of blobs for instances that are stored again.
+    pub replacement_policy: ReplacementPolicyConfig,
+
+    /// Accept and check instances without persisting them, for connectivity
+    /// and data-quality testing against a non-production node.
+    pub validate_only: bool,
+
+    /// Number of hash-prefix directory levels placed above study directories
+    /// for newly stored instances (0-4). Already stored files keep their paths.
+    pub storage_shard_levels: u8,
+
+    /// External scanner run over each received instance before it is stored.
+    pub content_scanner: Option<ContentScannerConfig>,
+}
+
+/// External command used to scan received payloads.
+///
+/// The command is run with the payload file path appended as its last
+/// argument. Exit status 0 accepts the instance; any other status rejects it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ContentScannerConfig {
+    /// Program and leading arguments, e.g. `["clamdscan", "--no-summary"]`.
+    pub command: Vec<String>,
+
+    /// Seconds a single scan may run before the instance is refused; must be
+    /// at least 1.
+    pub timeout_seconds: u64,
+
+    /// Maximum number of scans running at the same time.
+    pub max_concurrent_scans: usize,
+
+    /// SOP Class UIDs accepted without scanning.
+    pub skip_sop_class_uids: Vec<String>,
+
+    /// Directory receiving a copy of each rejected payload, named
+    /// `<sop-instance-uid>.<rejection-nanos>.dcm`.
+    pub quarantine_dir: Option<PathBuf>,
+}
+
+impl Default for ContentScannerConfig {
+    fn default() -> Self {
+        Self {
+            command: Vec::new(),
+            timeout_seconds: 30,
+            max_concurrent_scans: 4,
+            skip_sop_class_uids: Vec::new(),
+            quarantine_dir: None,
+        }
+    }
+}
+
+/// Supported handling of re-stored instances.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementPolicyConfig {
+    /// Overwrite the existing blob in place.
+    #[default]
+    Overwrite,
+
+    /// Keep earlier blobs and write each replacement to a new versioned key.
+    KeepVersions,
+}
+
+/// Limits applied to a single study instead of the ingest defaults.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StudyLimitOverrideConfig {
+    /// Study Instance UID the override applies to.
+    pub study_instance_uid: String,
+
+    /// Maximum number of instances, or unlimited when omitted.
+    #[serde(default)]
+    pub max_instances: Option<u64>,
+
+    /// Maximum total stored bytes, or unlimited when omitted.
+    #[serde(default)]
+    pub max_size_bytes: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use config::{Config, File, FileFormat};
+
+    use super::{IngestConfig, ReplacementPolicyConfig};
+
+    #[test]
+    fn ingest_defaults_to_unlimited_studies() {
+        let config = IngestConfig::default();
+        assert_eq!(config.max_instances_per_study, None);
+        assert_eq!(config.max_study_size_bytes, None);
+        assert!(config.study_limit_overrides.is_empty());
+        assert_eq!(config.max_instance_size_bytes, None);
+        assert_eq!(
+            config.replacement_policy,
+            ReplacementPolicyConfig::Overwrite
+        );
+        assert!(!config.validate_only);
+        assert_eq!(config.storage_shard_levels, 0);
+        assert!(config.content_scanner.is_none());
+    }
+
+    #[test]
+    fn ingest_parses_study_limit_overrides() {
+        let config: IngestConfig = Config::builder()
+            .add_source(File::from_str(
+                r#"
+                max_instances_per_study = 5000
+                max_instance_size_bytes = 1073741824
+                replacement_policy = "keep_versions"
+                validate_only = true
+                storage_shard_levels = 2
+
+                [content_scanner]
+                command = ["clamdscan", "--no-summary"]
+                skip_sop_class_uids = ["1.2.840.10008.5.1.4.1.1.88.11"]
+                quarantine_dir = "quarantine"
+
+                [[study_limit_overrides]]
+                study_instance_uid = "1.2.3"
+                max_instances = 20000
+                "#,
+                FileFormat::Toml,
+            ))
+            .build()
+            .expect("config")
+            .try_deserialize()
+            .expect("ingest config");
+
+        assert_eq!(config.max_instances_per_study, Some(5000));
+        assert_eq!(config.max_instance_size_bytes, Some(1 << 30));
+        assert_eq!(
+            config.replacement_policy,
+            ReplacementPolicyConfig::KeepVersions
+        );
+        assert!(config.validate_only);
+        assert_eq!(config.storage_shard_levels, 2);
+        assert_eq!(config.study_limit_overrides.len(), 1);
+        assert_eq!(config.study_limit_overrides[0].study_instance_uid, "1.2.3");
+        assert_eq!(config.study_limit_overrides[0].max_instances, Some(20000));
+        assert_eq!(config.study_limit_overrides[0].max_size_bytes, None);
+
+        let scanner = config.content_scanner.expect("content scanner");
+        assert_eq!(scanner.command, ["clamdscan", "--no-summary"]);
+        assert_eq!(scanner.timeout_seconds, 30);
+        assert_eq!(scanner.max_concurrent_scans, 4);
+        assert_eq!(
+            scanner.skip_sop_class_uids,
+            ["1.2.840.10008.5.1.4.1.1.88.11"]
+        );
+        assert_eq!(scanner.quarantine_dir, Some(PathBuf::from("quarantine")));
+    }
+}
@@ -7,7 +7,9 @@
 pub mod application_entity;
 pub mod database;
 pub mod error;
+pub mod ingest;
 pub mod monolith;
+pub mod query;
 pub mod runtime;
 pub mod storage;
 pub mod telemetry;
@@ -5,6 +5,8 @@
 use crate::app::AppConfig;
 use crate::application_entity::ApplicationEntitiesConfig;
 use crate::database::DatabaseConfig;
+use crate::ingest::IngestConfig;
+use crate::query::QueryConfig;
 use crate::runtime::RuntimeConfig;
 use crate::storage::{FilesystemConfig, StorageConfig};
 use crate::telemetry::TelemetryConfig;
@@ -34,6 +36,12 @@ pub struct MonolithConfig {
     /// Selected blob storage backend configuration.
     pub storage: StorageConfig,
 
+    /// Ingest service behaviour configuration.
+    pub ingest: IngestConfig,
+
+    /// Query service behaviour configuration.
+    pub query: QueryConfig,
+
     /// Telemetry configuration, including logs, traces, and metrics.
     pub telemetry: TelemetryConfig,
 }
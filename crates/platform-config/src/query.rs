@@ -0,0 +1,65 @@
+use serde::Deserialize;
+
+/// Query service behaviour configuration.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryConfig {
+    /// Target used when matching Study Date keys.
+    pub study_date_matching: StudyDateMatchingConfig,
+
+    /// Handling of catalog matches that cannot be shaped into a response.
+    pub invalid_match_handling: InvalidMatchHandlingConfig,
+
+    /// Enables the non-standard `!value` exclusion syntax for C-FIND keys.
+    pub exclusion_matching: bool,
+
+    /// Retrieve AE Title advertised in C-FIND responses instead of the called AE title.
+    pub retrieve_ae_title: Option<String>,
+}
+
+/// Supported Study Date matching targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudyDateMatchingConfig {
+    /// Match the literal DA values as received (standards-compliant).
+    #[default]
+    Literal,
+
+    /// Match Study Date and Study Time against the UTC study timestamp
+    /// normalized at ingest.
+    Utc,
+}
+
+/// Supported handling of catalog matches that cannot be shaped into a response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvalidMatchHandlingConfig {
+    /// Log and omit the match, returning the remaining matches.
+    #[default]
+    Skip,
+
+    /// Fail the whole C-FIND request.
+    FailFast,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidMatchHandlingConfig, QueryConfig, StudyDateMatchingConfig};
+
+    #[test]
+    fn query_defaults_to_literal_study_date_matching() {
+        let config = QueryConfig::default();
+        assert_eq!(config.study_date_matching, StudyDateMatchingConfig::Literal);
+    }
+
+    #[test]
+    fn query_defaults_to_skipping_invalid_matches() {
+        let config = QueryConfig::default();
+        assert_eq!(
+            config.invalid_match_handling,
+            InvalidMatchHandlingConfig::Skip
+        );
+        assert!(!config.exclusion_matching);
+        assert_eq!(config.retrieve_ae_title, None);
+    }
+}
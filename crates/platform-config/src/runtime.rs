@@ -36,6 +36,9 @@ pub struct RuntimeDimseConfig {
 
     /// Seconds to wait for concurrency permits before closing an accepted socket.
     pub permit_wait_timeout_seconds: u64,
+
+    /// DIMSE service classes registered on every local AE.
+    pub services: DimseServicesConfig,
 }
 
 impl Default for RuntimeDimseConfig {
@@ -43,6 +46,38 @@ fn default() -> Self {
         Self {
             global_max_concurrent_associations: 1024,
             permit_wait_timeout_seconds: 5,
+            services: DimseServicesConfig::default(),
+        }
+    }
+}
+
+/// DIMSE service class enablement.
+///
+/// Disabled services are not registered, so their SOP classes are rejected
+/// during association negotiation. Changes take effect on restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct DimseServicesConfig {
+    /// Verification (C-ECHO).
+    pub verification: bool,
+
+    /// Query (C-FIND).
+    pub query: bool,
+
+    /// Storage (C-STORE).
+    pub storage: bool,
+
+    /// Retrieve (C-GET and C-MOVE).
+    pub retrieve: bool,
+}
+
+impl Default for DimseServicesConfig {
+    fn default() -> Self {
+        Self {
+            verification: true,
+            query: true,
+            storage: true,
+            retrieve: true,
         }
     }
 }
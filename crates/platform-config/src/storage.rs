@@ -17,12 +17,16 @@ pub enum StorageConfig {
 pub struct FilesystemConfig {
     /// Root directory containing archived blob payloads.
     pub root: PathBuf,
+    /// Seconds between walks of the root that record directory and file
+    /// count metrics. Disabled when unset; each walk visits every directory.
+    pub layout_metrics_interval_secs: Option<u64>,
 }
 
 impl Default for FilesystemConfig {
     fn default() -> Self {
         Self {
             root: PathBuf::from("data"),
+            layout_metrics_interval_secs: None,
         }
     }
 }
@@ -37,6 +41,7 @@ mod tests {
     fn filesystem_defaults_to_repo_relative_root() {
         let config = FilesystemConfig::default();
         assert_eq!(config.root, PathBuf::from("data"));
+        assert_eq!(config.layout_metrics_interval_secs, None);
     }
 
     #[test]
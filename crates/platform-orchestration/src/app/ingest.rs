@@ -1,19 +1,173 @@
 use std::sync::Arc;
 
-use rustcoon_ingest::{HierarchicalInstanceKeyResolver, IngestService};
+use rustcoon_config::ingest::{IngestConfig, ReplacementPolicyConfig};
+use rustcoon_dicom::StudyInstanceUid;
+use rustcoon_ingest::{
+    HierarchicalInstanceKeyResolver, IngestService, ReplacementPolicy, StudyLimit, StudyLimits,
+};
 use rustcoon_storage::BlobStore;
 
+use crate::OrchestratorError;
 use crate::infrastructure::index::CatalogPorts;
+use crate::infrastructure::scanner::CommandContentScanner;
 
 /// Builds ingest service from shared infrastructure handles.
 pub fn build_ingest_service(
     blob_store: Arc<dyn BlobStore>,
     catalog_ports: &CatalogPorts,
-) -> Arc<IngestService> {
-    Arc::new(IngestService::new(
+    config: &IngestConfig,
+) -> Result<Arc<IngestService>, OrchestratorError> {
+    if config.storage_shard_levels > HierarchicalInstanceKeyResolver::MAX_SHARD_LEVELS {
+        return Err(OrchestratorError::InvalidConfiguration(format!(
+            "ingest storage_shard_levels must be at most {}",
+            HierarchicalInstanceKeyResolver::MAX_SHARD_LEVELS
+        )));
+    }
+    if config.validate_only {
+        tracing::warn!(
+            "ingest.validate_only is enabled: instances are validated and acknowledged \
+             but NOT stored or indexed"
+        );
+    }
+    let mut service = IngestService::new(
         blob_store,
-        Arc::clone(&catalog_ports.0),
-        Arc::clone(&catalog_ports.1),
-        Arc::new(HierarchicalInstanceKeyResolver::new()),
-    ))
+        Arc::clone(&catalog_ports.read),
+        Arc::clone(&catalog_ports.write),
+        Arc::new(
+            HierarchicalInstanceKeyResolver::new().with_shard_levels(config.storage_shard_levels),
+        ),
+    )
+    .with_study_limits(build_study_limits(config)?)
+    .with_replacement_policy(match config.replacement_policy {
+        ReplacementPolicyConfig::Overwrite => ReplacementPolicy::Overwrite,
+        ReplacementPolicyConfig::KeepVersions => ReplacementPolicy::KeepVersions,
+    })
+    .with_validate_only(config.validate_only);
+    if let Some(max_size_bytes) = max_instance_size_bytes(config)? {
+        service = service.with_max_instance_size_bytes(max_size_bytes);
+    }
+    if let Some(scanner) = &config.content_scanner {
+        service =
+            service.with_content_scanner(Arc::new(CommandContentScanner::from_config(scanner)?));
+    }
+    Ok(Arc::new(service))
+}
+
+/// Smallest accepted per-instance size limit; anything lower is almost
+/// certainly a unit mistake and would reject ordinary images.
+const MIN_INSTANCE_SIZE_BYTES: u64 = 1 << 20;
+
+fn max_instance_size_bytes(config: &IngestConfig) -> Result<Option<u64>, OrchestratorError> {
+    match config.max_instance_size_bytes {
+        Some(max_size_bytes) if max_size_bytes < MIN_INSTANCE_SIZE_BYTES => {
+            Err(OrchestratorError::InvalidConfiguration(format!(
+                "ingest max_instance_size_bytes must be at least {MIN_INSTANCE_SIZE_BYTES} \
+                 (omit it for no limit), got {max_size_bytes}"
+            )))
+        }
+        limit => Ok(limit),
+    }
+}
+
+fn build_study_limits(config: &IngestConfig) -> Result<StudyLimits, OrchestratorError> {
+    let default = StudyLimit {
+        max_instances: config.max_instances_per_study,
+        max_size_bytes: config.max_study_size_bytes,
+    };
+    config
+        .study_limit_overrides
+        .iter()
+        .try_fold(StudyLimits::new(default), |limits, entry| {
+            let study_instance_uid = StudyInstanceUid::new(entry.study_instance_uid.clone())
+                .map_err(|error| {
+                    OrchestratorError::InvalidConfiguration(format!(
+                        "invalid study limit override UID '{}': {error}",
+                        entry.study_instance_uid
+                    ))
+                })?;
+            Ok(limits.with_override(
+                study_instance_uid,
+                StudyLimit {
+                    max_instances: entry.max_instances,
+                    max_size_bytes: entry.max_size_bytes,
+                },
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rustcoon_config::ingest::{IngestConfig, StudyLimitOverrideConfig};
+    use rustcoon_dicom::StudyInstanceUid;
+    use rustcoon_ingest::StudyLimit;
+
+    use super::{build_study_limits, max_instance_size_bytes};
+
+    #[test]
+    fn study_limits_apply_defaults_and_overrides() {
+        let config = IngestConfig {
+            max_instances_per_study: Some(100),
+            max_study_size_bytes: None,
+            study_limit_overrides: vec![StudyLimitOverrideConfig {
+                study_instance_uid: "1.2.3".to_string(),
+                max_instances: None,
+                max_size_bytes: Some(1_024),
+            }],
+            ..IngestConfig::default()
+        };
+
+        let limits = build_study_limits(&config).expect("study limits");
+
+        assert_eq!(
+            limits.for_study(&StudyInstanceUid::new("1.2.4").unwrap()),
+            StudyLimit {
+                max_instances: Some(100),
+                max_size_bytes: None,
+            }
+        );
+        assert_eq!(
+            limits.for_study(&StudyInstanceUid::new("1.2.3").unwrap()),
+            StudyLimit {
+                max_instances: None,
+                max_size_bytes: Some(1_024),
+            }
+        );
+    }
+
+    #[test]
+    fn study_limits_reject_invalid_override_uids() {
+        let config = IngestConfig {
+            study_limit_overrides: vec![StudyLimitOverrideConfig {
+                study_instance_uid: "not a uid".to_string(),
+                max_instances: Some(1),
+                max_size_bytes: None,
+            }],
+            ..IngestConfig::default()
+        };
+
+        assert!(matches!(
+            build_study_limits(&config),
+            Err(crate::OrchestratorError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn instance_size_limit_rejects_values_below_one_mebibyte() {
+        let limit = |configured: Option<u64>| {
+            max_instance_size_bytes(&IngestConfig {
+                max_instance_size_bytes: configured,
+                ..IngestConfig::default()
+            })
+        };
+
+        assert_eq!(limit(None).expect("unlimited"), None);
+        assert_eq!(limit(Some(1 << 20)).expect("minimum"), Some(1 << 20));
+        assert_eq!(limit(Some(4 << 30)).expect("4 GiB"), Some(4 << 30));
+        for too_small in [0, 1, 4096] {
+            assert!(matches!(
+                limit(Some(too_small)),
+                Err(crate::OrchestratorError::InvalidConfiguration(_))
+            ));
+        }
+    }
 }
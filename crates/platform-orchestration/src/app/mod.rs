@@ -1,3 +1,4 @@
 pub mod ingest;
 pub mod query;
 pub mod retrieve;
+pub mod versions;
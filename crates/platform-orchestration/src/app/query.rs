@@ -1,10 +1,80 @@
 use std::sync::Arc;
 
-use rustcoon_query::QueryService;
+use rustcoon_application_entity::AeTitle;
+use rustcoon_config::query::{InvalidMatchHandlingConfig, QueryConfig, StudyDateMatchingConfig};
+use rustcoon_query::{InvalidMatchHandling, QueryService, StudyDateMatching};
 
+use crate::OrchestratorError;
 use crate::infrastructure::index::CatalogPorts;
 
 /// Builds query service from shared catalog infrastructure handles.
-pub fn build_query_service(catalog_ports: &CatalogPorts) -> Arc<QueryService> {
-    Arc::new(QueryService::new(Arc::clone(&catalog_ports.0)))
+pub fn build_query_service(
+    catalog_ports: &CatalogPorts,
+    config: &QueryConfig,
+) -> Result<Arc<QueryService>, OrchestratorError> {
+    let study_date_matching = match config.study_date_matching {
+        StudyDateMatchingConfig::Literal => StudyDateMatching::Literal,
+        StudyDateMatchingConfig::Utc => StudyDateMatching::NormalizedUtc,
+    };
+    let invalid_match_handling = match config.invalid_match_handling {
+        InvalidMatchHandlingConfig::Skip => InvalidMatchHandling::Skip,
+        InvalidMatchHandlingConfig::FailFast => InvalidMatchHandling::FailFast,
+    };
+    let service = QueryService::new(Arc::clone(&catalog_ports.replica_read))
+        .with_study_date_matching(study_date_matching)
+        .with_invalid_match_handling(invalid_match_handling)
+        .with_exclusion_matching(config.exclusion_matching);
+    match retrieve_ae_title(config)? {
+        Some(ae_title) => Ok(Arc::new(service.with_retrieve_ae_title(ae_title.as_str()))),
+        None => Ok(Arc::new(service)),
+    }
+}
+
+/// Validates the configured Retrieve AE Title up front, so a bad value fails
+/// startup instead of every C-FIND that would advertise it.
+fn retrieve_ae_title(config: &QueryConfig) -> Result<Option<AeTitle>, OrchestratorError> {
+    config
+        .retrieve_ae_title
+        .as_deref()
+        .map(|ae_title| {
+            ae_title.trim().parse::<AeTitle>().map_err(|error| {
+                OrchestratorError::InvalidConfiguration(format!(
+                    "invalid query retrieve_ae_title '{ae_title}': {error}"
+                ))
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use rustcoon_config::query::QueryConfig;
+
+    use super::retrieve_ae_title;
+    use crate::OrchestratorError;
+
+    #[test]
+    fn retrieve_ae_title_is_trimmed_and_validated() {
+        let title = |value: Option<&str>| {
+            retrieve_ae_title(&QueryConfig {
+                retrieve_ae_title: value.map(str::to_string),
+                ..QueryConfig::default()
+            })
+        };
+
+        assert_eq!(title(None).expect("no title"), None);
+        assert_eq!(
+            title(Some(" ARCHIVE_GW "))
+                .expect("title")
+                .unwrap()
+                .as_str(),
+            "ARCHIVE_GW"
+        );
+        for invalid in ["  ", "AN_AE_TITLE_TOO_LONG", "BAD\\AE"] {
+            assert!(matches!(
+                title(Some(invalid)),
+                Err(OrchestratorError::InvalidConfiguration(_))
+            ));
+        }
+    }
 }
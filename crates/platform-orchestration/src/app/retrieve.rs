@@ -12,7 +12,7 @@ pub fn build_retrieve_service(
 ) -> Arc<RetrieveService> {
     let blob_read_store: Arc<dyn rustcoon_storage::BlobReadStore> = blob_store;
     Arc::new(RetrieveService::new(
-        Arc::clone(&catalog_ports.0),
+        Arc::clone(&catalog_ports.replica_read),
         blob_read_store,
     ))
 }
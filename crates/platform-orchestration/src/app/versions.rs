@@ -0,0 +1,131 @@
+use rustcoon_dicom::SopInstanceUid;
+use rustcoon_index::StoredObjectRef;
+use rustcoon_storage::BlobKey;
+
+use crate::core::OrchestratorError;
+use crate::infrastructure::index::CatalogPorts;
+
+/// Lists the blobs an instance referenced before it was re-stored under
+/// another key, newest first.
+pub async fn list_instance_versions(
+    catalog_ports: &CatalogPorts,
+    sop_instance_uid: &str,
+) -> Result<Vec<StoredObjectRef>, OrchestratorError> {
+    let sop_instance_uid = parse_sop_instance_uid(sop_instance_uid)?;
+    catalog_ports
+        .read
+        .list_superseded_blobs(&sop_instance_uid)
+        .await
+        .map_err(|error| OrchestratorError::Maintenance(error.to_string()))
+}
+
+/// Makes a superseded blob the instance's current blob again. The blob it
+/// replaces becomes superseded in turn. Catalog attributes keep describing the
+/// most recent store; only the retrieved payload changes.
+pub async fn restore_instance_version(
+    catalog_ports: &CatalogPorts,
+    sop_instance_uid: &str,
+    blob_key: &str,
+) -> Result<(), OrchestratorError> {
+    let blob_key = BlobKey::new(blob_key)
+        .map_err(|error| OrchestratorError::Maintenance(format!("invalid blob key: {error}")))?;
+    let versions = list_instance_versions(catalog_ports, sop_instance_uid).await?;
+    let Some(version) = versions.into_iter().find(|version| version.key == blob_key) else {
+        return Err(OrchestratorError::Maintenance(format!(
+            "{blob_key} is not a superseded blob of instance {sop_instance_uid}"
+        )));
+    };
+    let instance = catalog_ports
+        .read
+        .get_instance(&parse_sop_instance_uid(sop_instance_uid)?)
+        .await
+        .map_err(|error| OrchestratorError::Maintenance(error.to_string()))?
+        .ok_or_else(|| {
+            OrchestratorError::Maintenance(format!("instance {sop_instance_uid} is not cataloged"))
+        })?;
+    catalog_ports
+        .write
+        .attach_blob(instance.record.identity(), version)
+        .await
+        .map_err(|error| OrchestratorError::Maintenance(error.to_string()))
+}
+
+fn parse_sop_instance_uid(value: &str) -> Result<SopInstanceUid, OrchestratorError> {
+    SopInstanceUid::new(value).map_err(|error| {
+        OrchestratorError::Maintenance(format!("invalid SOP Instance UID: {error}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rustcoon_dicom::{
+        DicomInstanceIdentity, DicomInstanceMetadata, DicomInstanceRecord, DicomPatient,
+        DicomSeriesMetadata, DicomStudyMetadata, SeriesInstanceUid, SopClassUid, StudyInstanceUid,
+    };
+    use rustcoon_index::{CatalogReadStore, CatalogWriteStore, InstanceUpsertRequest};
+    use rustcoon_index_sqlite::{SqliteCatalogConfig, SqliteCatalogStore};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn restore_swaps_the_current_blob_with_a_superseded_one() {
+        let store = Arc::new(
+            SqliteCatalogStore::connect(
+                &SqliteCatalogConfig::new("sqlite::memory:").with_max_connections(1),
+            )
+            .await
+            .expect("connect"),
+        );
+        let catalog_ports = CatalogPorts {
+            read: store.clone() as Arc<dyn CatalogReadStore>,
+            write: store.clone() as Arc<dyn CatalogWriteStore>,
+            replica_read: store.clone(),
+        };
+        let record = DicomInstanceRecord::new(
+            DicomInstanceIdentity::new(
+                StudyInstanceUid::new("1.2.3").unwrap(),
+                SeriesInstanceUid::new("1.2.3.4").unwrap(),
+                SopInstanceUid::new("1.2.3.4.5").unwrap(),
+                SopClassUid::new("1.2.840.10008.5.1.4.1.1.2").unwrap(),
+            ),
+            DicomPatient::new(None, None),
+            DicomStudyMetadata::new(None, None),
+            DicomSeriesMetadata::new(Some("CT".to_string()), None),
+            DicomInstanceMetadata::new(None, None),
+        );
+        let blob = |key: &str| StoredObjectRef::new(BlobKey::new(key).unwrap());
+        for key in ["instances/v1.dcm", "instances/v2.dcm"] {
+            store
+                .upsert_instance(InstanceUpsertRequest::new(record.clone()).with_blob(blob(key)))
+                .await
+                .expect("store");
+        }
+        let keys = async || {
+            list_instance_versions(&catalog_ports, "1.2.3.4.5")
+                .await
+                .expect("list versions")
+                .into_iter()
+                .map(|version| version.key.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys().await, ["instances/v1.dcm"]);
+
+        let unknown =
+            restore_instance_version(&catalog_ports, "1.2.3.4.5", "instances/v3.dcm").await;
+        assert!(matches!(unknown, Err(OrchestratorError::Maintenance(_))));
+
+        restore_instance_version(&catalog_ports, "1.2.3.4.5", "instances/v1.dcm")
+            .await
+            .expect("restore");
+        assert_eq!(keys().await, ["instances/v2.dcm"]);
+        let current = store
+            .get_instance(&SopInstanceUid::new("1.2.3.4.5").unwrap())
+            .await
+            .expect("get instance")
+            .and_then(|instance| instance.blob)
+            .expect("blob");
+        assert_eq!(current.key.to_string(), "instances/v1.dcm");
+    }
+}
@@ -20,6 +20,9 @@ pub enum OrchestratorError {
 
     #[error("at least one local AE must be configured")]
     MissingLocalAe,
+
+    #[error("maintenance command failed: {0}")]
+    Maintenance(String),
 }
 
 #[cfg(test)]
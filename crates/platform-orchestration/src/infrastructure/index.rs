@@ -1,13 +1,23 @@
 use std::sync::Arc;
 
-use rustcoon_config::database::DatabaseBackendConfig;
+use rustcoon_config::database::{DatabaseBackendConfig, PostgresDatabaseConfig};
 use rustcoon_index::{CatalogReadStore, CatalogWriteStore};
 use rustcoon_index_postgres::{PostgresCatalogConfig, PostgresCatalogStore};
 use rustcoon_index_sqlite::{SqliteCatalogConfig, SqliteCatalogStore};
 
 use crate::core::OrchestratorError;
 
-pub type CatalogPorts = (Arc<dyn CatalogReadStore>, Arc<dyn CatalogWriteStore>);
+/// Catalog handles shared by the application services.
+pub struct CatalogPorts {
+    /// Primary read port; always observes committed writes.
+    pub read: Arc<dyn CatalogReadStore>,
+
+    pub write: Arc<dyn CatalogWriteStore>,
+
+    /// Read port for query and retrieve traffic. Points at the read replica
+    /// when one is configured and at the primary otherwise.
+    pub replica_read: Arc<dyn CatalogReadStore>,
+}
 
 /// Builds shared catalog ports when a database backend is configured.
 pub async fn build_catalog_ports(
@@ -16,10 +26,10 @@ pub async fn build_catalog_ports(
     match &config.database.backend {
         DatabaseBackendConfig::Postgres(postgres) => {
             let catalog_store = Arc::new(
-                PostgresCatalogStore::connect(
-                    &PostgresCatalogConfig::new(postgres.connection_string.clone())
-                        .with_max_connections(postgres.max_connections),
-                )
+                PostgresCatalogStore::connect(&postgres_catalog_config(
+                    &postgres.connection_string,
+                    postgres,
+                ))
                 .await
                 .map_err(|error| {
                     OrchestratorError::Infrastructure(format!(
@@ -27,9 +37,34 @@ pub async fn build_catalog_ports(
                     ))
                 })?,
             );
-            let catalog_read: Arc<dyn CatalogReadStore> = catalog_store.clone();
-            let catalog_write: Arc<dyn CatalogWriteStore> = catalog_store;
-            Ok((catalog_read, catalog_write))
+            // Only the primary is writable; replicas receive the backfill
+            // through replication.
+            catalog_store.run_backfills().await.map_err(|error| {
+                OrchestratorError::Infrastructure(format!(
+                    "failed to backfill Postgres catalog columns: {error}"
+                ))
+            })?;
+            let replica_read: Arc<dyn CatalogReadStore> =
+                match &postgres.read_replica_connection_string {
+                    Some(connection_string) => Arc::new(
+                        PostgresCatalogStore::connect(&postgres_catalog_config(
+                            connection_string,
+                            postgres,
+                        ))
+                        .await
+                        .map_err(|error| {
+                            OrchestratorError::Infrastructure(format!(
+                                "failed to connect Postgres read replica: {error}"
+                            ))
+                        })?,
+                    ),
+                    None => catalog_store.clone(),
+                };
+            Ok(CatalogPorts {
+                read: catalog_store.clone(),
+                write: catalog_store,
+                replica_read,
+            })
         }
         DatabaseBackendConfig::Sqlite(sqlite) => {
             let path = config.filesystem.root.join("catalog.db");
@@ -56,7 +91,8 @@ pub async fn build_catalog_ports(
             let catalog_store = Arc::new(
                 SqliteCatalogStore::connect(
                     &SqliteCatalogConfig::new(connection_string)
-                        .with_max_connections(sqlite.max_connections),
+                        .with_max_connections(sqlite.max_connections)
+                        .with_auto_migrate(sqlite.auto_migrate),
                 )
                 .await
                 .map_err(|error| {
@@ -65,9 +101,64 @@ pub async fn build_catalog_ports(
                     ))
                 })?,
             );
-            let catalog_read: Arc<dyn CatalogReadStore> = catalog_store.clone();
-            let catalog_write: Arc<dyn CatalogWriteStore> = catalog_store;
-            Ok((catalog_read, catalog_write))
+            Ok(CatalogPorts {
+                read: catalog_store.clone(),
+                write: catalog_store.clone(),
+                replica_read: catalog_store,
+            })
         }
     }
 }
+
+/// Applies the shared pool settings to one Postgres endpoint.
+fn postgres_catalog_config(
+    connection_string: &str,
+    postgres: &PostgresDatabaseConfig,
+) -> PostgresCatalogConfig {
+    let config = PostgresCatalogConfig::new(connection_string)
+        .with_max_connections(postgres.max_connections);
+    match &postgres.schema {
+        Some(schema) => config.with_schema(schema.clone()),
+        None => config,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rustcoon_config::database::PostgresDatabaseConfig;
+
+    use super::{build_catalog_ports, postgres_catalog_config};
+
+    #[test]
+    fn postgres_replica_shares_pool_settings_with_primary() {
+        let postgres = PostgresDatabaseConfig {
+            max_connections: 4,
+            schema: Some("rustcoon".to_string()),
+            read_replica_connection_string: Some("postgres://replica/rustcoon".to_string()),
+            ..PostgresDatabaseConfig::default()
+        };
+
+        let replica = postgres_catalog_config("postgres://replica/rustcoon", &postgres);
+
+        assert_eq!(replica.connection_string(), "postgres://replica/rustcoon");
+        assert_eq!(replica.max_connections(), 4);
+        assert_eq!(replica.schema(), Some("rustcoon"));
+    }
+
+    #[tokio::test]
+    async fn sqlite_reads_use_the_primary_store() {
+        let root = std::env::temp_dir().join(format!(
+            "rustcoon-orchestration-catalog-ports-{}",
+            std::process::id()
+        ));
+        let mut config = rustcoon_config::MonolithConfig::default();
+        config.filesystem.root = root.clone();
+
+        let ports = build_catalog_ports(&config).await.expect("catalog ports");
+
+        assert!(Arc::ptr_eq(&ports.read, &ports.replica_read));
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
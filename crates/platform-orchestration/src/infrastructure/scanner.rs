@@ -0,0 +1,286 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use rustcoon_config::ingest::ContentScannerConfig;
+use rustcoon_dicom::DicomInstanceRecord;
+use rustcoon_ingest::{ContentScanner, ScanVerdict};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+
+use crate::OrchestratorError;
+
+/// Content scanner that runs an external command on each payload file.
+#[derive(Debug)]
+pub struct CommandContentScanner {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+    permits: Semaphore,
+    skip_sop_class_uids: Vec<String>,
+    quarantine_dir: Option<PathBuf>,
+}
+
+impl CommandContentScanner {
+    pub fn from_config(config: &ContentScannerConfig) -> Result<Self, OrchestratorError> {
+        let Some((program, args)) = config.command.split_first() else {
+            return Err(OrchestratorError::InvalidConfiguration(
+                "ingest content_scanner command must not be empty".to_string(),
+            ));
+        };
+        if config.max_concurrent_scans == 0 {
+            return Err(OrchestratorError::InvalidConfiguration(
+                "ingest content_scanner max_concurrent_scans must be at least 1".to_string(),
+            ));
+        }
+        if config.timeout_seconds == 0 {
+            return Err(OrchestratorError::InvalidConfiguration(
+                "ingest content_scanner timeout_seconds must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            program: program.clone(),
+            args: args.to_vec(),
+            timeout: Duration::from_secs(config.timeout_seconds),
+            permits: Semaphore::new(config.max_concurrent_scans),
+            skip_sop_class_uids: config.skip_sop_class_uids.clone(),
+            quarantine_dir: config.quarantine_dir.clone(),
+        })
+    }
+
+    /// Copies a rejected payload into the quarantine directory. Names carry
+    /// the rejection time so repeated rejections of one SOP instance are kept.
+    async fn quarantine(&self, record: &DicomInstanceRecord, payload: &Path) {
+        let Some(dir) = &self.quarantine_dir else {
+            return;
+        };
+        let rejected_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        let target = dir.join(format!(
+            "{}.{rejected_at}.dcm",
+            record.identity().sop_instance_uid().as_str()
+        ));
+        let copied = match tokio::fs::create_dir_all(dir).await {
+            Ok(()) => tokio::fs::copy(payload, &target).await.map(|_| ()),
+            Err(error) => Err(error),
+        };
+        if let Err(error) = copied {
+            tracing::warn!(
+                path = %target.display(),
+                error = %error,
+                "failed to quarantine rejected payload"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl ContentScanner for CommandContentScanner {
+    async fn scan(
+        &self,
+        record: &DicomInstanceRecord,
+        payload: &Path,
+    ) -> Result<ScanVerdict, io::Error> {
+        let sop_class_uid = record.identity().sop_class_uid().as_str();
+        if self
+            .skip_sop_class_uids
+            .iter()
+            .any(|uid| uid == sop_class_uid)
+        {
+            return Ok(ScanVerdict::Skipped);
+        }
+
+        let _permit = self.permits.acquire().await.map_err(io::Error::other)?;
+        let child = Command::new(&self.program)
+            .args(&self.args)
+            .arg(payload)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "content scan timed out"))??;
+
+        if output.status.success() {
+            return Ok(ScanVerdict::Clean);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stdout
+            .lines()
+            .chain(stderr.lines())
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("scanner exited with {}", output.status));
+        self.quarantine(record, payload).await;
+        Ok(ScanVerdict::Rejected { reason })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rustcoon_config::ingest::ContentScannerConfig;
+    use rustcoon_dicom::{
+        DicomInstanceIdentity, DicomInstanceMetadata, DicomInstanceRecord, DicomPatient,
+        DicomSeriesMetadata, DicomStudyMetadata, SeriesInstanceUid, SopClassUid, SopInstanceUid,
+        StudyInstanceUid,
+    };
+    use rustcoon_ingest::{ContentScanner, ScanVerdict};
+    use tempfile::NamedTempFile;
+
+    use super::CommandContentScanner;
+
+    const SCRIPT: &str = "if grep -q INFECTED \"$0\"; then echo 'Win.Test.EICAR FOUND'; exit 1; fi";
+
+    fn record(sop_class_uid: &str) -> DicomInstanceRecord {
+        DicomInstanceRecord::new(
+            DicomInstanceIdentity::new(
+                StudyInstanceUid::new("1.2.3").unwrap(),
+                SeriesInstanceUid::new("1.2.3.1").unwrap(),
+                SopInstanceUid::new("1.2.3.1.1").unwrap(),
+                SopClassUid::new(sop_class_uid).unwrap(),
+            ),
+            DicomPatient::default(),
+            DicomStudyMetadata::default(),
+            DicomSeriesMetadata::default(),
+            DicomInstanceMetadata::default(),
+        )
+    }
+
+    fn payload(contents: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().expect("payload");
+        file.write_all(contents).expect("write payload");
+        file
+    }
+
+    fn shell_config(script: &str) -> ContentScannerConfig {
+        ContentScannerConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            ..ContentScannerConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn command_scanner_maps_exit_status_to_verdict_and_quarantines() {
+        let quarantine = tempfile::tempdir().expect("quarantine dir");
+        let scanner = CommandContentScanner::from_config(&ContentScannerConfig {
+            quarantine_dir: Some(quarantine.path().join("rejected")),
+            ..shell_config(SCRIPT)
+        })
+        .expect("scanner");
+        let record = record("1.2.840.10008.5.1.4.1.1.2");
+
+        let clean = payload(b"DICM clean");
+        assert_eq!(
+            scanner.scan(&record, clean.path()).await.expect("scan"),
+            ScanVerdict::Clean
+        );
+
+        let infected = payload(b"DICM INFECTED");
+        assert_eq!(
+            scanner.scan(&record, infected.path()).await.expect("scan"),
+            ScanVerdict::Rejected {
+                reason: "Win.Test.EICAR FOUND".to_string()
+            }
+        );
+        assert_eq!(
+            scanner.scan(&record, infected.path()).await.expect("scan"),
+            ScanVerdict::Rejected {
+                reason: "Win.Test.EICAR FOUND".to_string()
+            }
+        );
+        let quarantined = std::fs::read_dir(quarantine.path().join("rejected"))
+            .expect("quarantine dir")
+            .map(|entry| entry.expect("entry").path())
+            .collect::<Vec<_>>();
+        assert_eq!(quarantined.len(), 2);
+        for path in quarantined {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            assert!(name.starts_with("1.2.3.1.1.") && name.ends_with(".dcm"));
+            assert_eq!(std::fs::read(path).expect("quarantined"), b"DICM INFECTED");
+        }
+    }
+
+    #[tokio::test]
+    async fn command_scanner_skips_listed_sop_classes() {
+        let scanner = CommandContentScanner::from_config(&ContentScannerConfig {
+            skip_sop_class_uids: vec!["1.2.840.10008.5.1.4.1.1.88.11".to_string()],
+            ..shell_config("exit 1")
+        })
+        .expect("scanner");
+
+        let file = payload(b"DICM");
+        assert_eq!(
+            scanner
+                .scan(&record("1.2.840.10008.5.1.4.1.1.88.11"), file.path())
+                .await
+                .expect("scan"),
+            ScanVerdict::Skipped
+        );
+        assert!(matches!(
+            scanner
+                .scan(&record("1.2.840.10008.5.1.4.1.1.2"), file.path())
+                .await
+                .expect("scan"),
+            ScanVerdict::Rejected { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn command_scanner_reports_timeouts_and_missing_programs_as_errors() {
+        let file = payload(b"DICM");
+        let record = record("1.2.840.10008.5.1.4.1.1.2");
+
+        let slow = CommandContentScanner::from_config(&ContentScannerConfig {
+            timeout_seconds: 1,
+            ..shell_config("sleep 5")
+        })
+        .expect("scanner");
+        assert_eq!(
+            slow.scan(&record, file.path())
+                .await
+                .expect_err("timeout")
+                .kind(),
+            std::io::ErrorKind::TimedOut
+        );
+
+        let missing = CommandContentScanner::from_config(&ContentScannerConfig {
+            command: vec!["/nonexistent/rustcoon-scanner".to_string()],
+            ..ContentScannerConfig::default()
+        })
+        .expect("scanner");
+        assert!(missing.scan(&record, file.path()).await.is_err());
+    }
+
+    #[test]
+    fn command_scanner_rejects_empty_command_zero_concurrency_and_zero_timeout() {
+        assert!(matches!(
+            CommandContentScanner::from_config(&ContentScannerConfig::default()),
+            Err(crate::OrchestratorError::InvalidConfiguration(_))
+        ));
+        assert!(matches!(
+            CommandContentScanner::from_config(&ContentScannerConfig {
+                max_concurrent_scans: 0,
+                ..shell_config("true")
+            }),
+            Err(crate::OrchestratorError::InvalidConfiguration(_))
+        ));
+        assert!(matches!(
+            CommandContentScanner::from_config(&ContentScannerConfig {
+                timeout_seconds: 0,
+                ..shell_config("true")
+            }),
+            Err(crate::OrchestratorError::InvalidConfiguration(_))
+        ));
+    }
+}
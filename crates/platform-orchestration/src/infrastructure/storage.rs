@@ -1,8 +1,14 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use rustcoon_config::storage::StorageConfig;
+use rustcoon_config::storage::{FilesystemConfig, StorageConfig};
 use rustcoon_storage::BlobStore;
-use rustcoon_storage_filesystem::FilesystemBlobStore;
+use rustcoon_storage_filesystem::{FilesystemBlobStore, LayoutReport};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::warn;
+
+use crate::core::OrchestratorError;
 
 /// Builds the configured blob store backend.
 pub fn build_blob_store(config: &rustcoon_config::MonolithConfig) -> Arc<dyn BlobStore> {
@@ -11,3 +17,43 @@ pub fn build_blob_store(config: &rustcoon_config::MonolithConfig) -> Arc<dyn Blo
     };
     Arc::new(FilesystemBlobStore::new(filesystem.root.clone()))
 }
+
+/// Reports how directories and files are spread under the filesystem root.
+pub async fn storage_layout_report(
+    config: &rustcoon_config::MonolithConfig,
+) -> Result<LayoutReport, OrchestratorError> {
+    FilesystemBlobStore::new(config.filesystem.root.clone())
+        .layout_report()
+        .await
+        .map_err(|error| {
+            OrchestratorError::Maintenance(format!(
+                "failed to walk {}: {error}",
+                config.filesystem.root.display()
+            ))
+        })
+}
+
+/// Periodically walks the filesystem root to refresh the directory and file
+/// count metrics, when an interval is configured.
+pub fn start_storage_layout_metrics(
+    filesystem: &FilesystemConfig,
+    shutdown: CancellationToken,
+    task_tracker: &TaskTracker,
+) {
+    let Some(interval_secs) = filesystem.layout_metrics_interval_secs else {
+        return;
+    };
+    let store = FilesystemBlobStore::new(filesystem.root.clone());
+    task_tracker.spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => return,
+                _ = interval.tick() => {}
+            }
+            if let Err(error) = store.layout_report().await {
+                warn!(%error, "failed to walk filesystem store for layout metrics");
+            }
+        }
+    });
+}
@@ -9,8 +9,11 @@
 pub use app::ingest::build_ingest_service;
 pub use app::query::build_query_service;
 pub use app::retrieve::build_retrieve_service;
+pub use app::versions::{list_instance_versions, restore_instance_version};
 pub use infrastructure::index::build_catalog_ports;
-pub use infrastructure::storage::build_blob_store;
+pub use infrastructure::storage::{
+    build_blob_store, start_storage_layout_metrics, storage_layout_report,
+};
 pub use protocols::dimse::{
     DimseServiceSelection, build_dimse_service_registries, start_listener_for_ae,
 };
@@ -3,7 +3,7 @@
 use std::time::Duration;
 
 use rustcoon_application_entity::ApplicationEntityRegistry;
-use rustcoon_config::runtime::RuntimeDimseConfig;
+use rustcoon_config::runtime::{DimseServicesConfig, RuntimeDimseConfig};
 use rustcoon_dimse::{
     CGetServiceProvider, CMoveServiceProvider, DefaultErrorHandler, DimseError, DimseListener,
     QueryServiceProvider, ServiceClassRegistry, StorageServiceProvider,
@@ -37,6 +37,16 @@ pub const fn monolith_default() -> Self {
             retrieve: true,
         }
     }
+
+    /// Selects the service classes enabled in runtime configuration.
+    pub const fn from_config(config: &DimseServicesConfig) -> Self {
+        Self {
+            verification: config.verification,
+            query: config.query,
+            storage: config.storage,
+            retrieve: config.retrieve,
+        }
+    }
 }
 
 /// Builds DIMSE registries using the requested provider selection profile.
@@ -96,6 +106,15 @@ pub fn build_dimse_service_registries(
                 Arc::clone(retrieve),
                 Arc::clone(&ae_registry),
             )));
+            if !selection.storage {
+                // C-GET sends its C-STORE sub-operations on storage contexts
+                // the requester proposes in the SCP role.
+                service_registry.accept_abstract_syntaxes(
+                    StorageServiceProvider::DEFAULT_STORAGE_SOP_CLASS_UIDS
+                        .iter()
+                        .copied(),
+                );
+            }
         }
         registries.insert(
             local.title().as_str().to_string(),
@@ -324,10 +343,17 @@ mod tests {
     use rustcoon_config::application_entity::{
         ApplicationEntitiesConfig, LocalApplicationEntityConfig, RemoteApplicationEntityConfig,
     };
-    use rustcoon_dimse::{ServiceClassRegistry, VerificationServiceProvider};
+    use rustcoon_config::runtime::DimseServicesConfig;
+    use rustcoon_dimse::{
+        QueryServiceProvider, ServiceClassRegistry, StorageServiceProvider,
+        VerificationServiceProvider,
+    };
 
     use crate::protocols::dimse::{DimseServiceSelection, build_dimse_service_registries};
-    use crate::start_listener_for_ae;
+    use crate::{
+        build_blob_store, build_catalog_ports, build_ingest_service, build_query_service,
+        build_retrieve_service, start_listener_for_ae,
+    };
 
     fn local(title: &str, bind: std::net::SocketAddr) -> LocalApplicationEntityConfig {
         LocalApplicationEntityConfig {
@@ -581,4 +607,75 @@ async fn build_service_registries_fails_when_query_selected_without_query_servic
             Err(crate::OrchestratorError::InvalidConfiguration(_))
         ));
     }
+
+    #[tokio::test]
+    async fn build_service_registries_registers_only_configured_services() {
+        const STUDY_ROOT_GET_SOP_CLASS_UID: &str = "1.2.840.10008.5.1.4.1.2.2.3";
+
+        let root = std::env::temp_dir().join(format!(
+            "rustcoon-orchestration-services-{}",
+            std::process::id()
+        ));
+        let mut config = rustcoon_config::MonolithConfig::default();
+        config.filesystem.root = root.clone();
+        config.application_entities.local = vec![local(
+            "RUSTCOON_A",
+            "127.0.0.1:11112".parse().expect("valid addr"),
+        )];
+        let ae_registry = Arc::new(
+            ApplicationEntityRegistry::try_from_config(&config.application_entities)
+                .expect("valid AE registry"),
+        );
+        let blob_store = build_blob_store(&config);
+        let catalog_ports = build_catalog_ports(&config).await.expect("catalog ports");
+        let ingest = build_ingest_service(blob_store.clone(), &catalog_ports, &config.ingest)
+            .expect("ingest service");
+        let query = build_query_service(&catalog_ports, &config.query).expect("query service");
+        let retrieve = build_retrieve_service(blob_store, &catalog_ports);
+
+        for mask in 0..16_u8 {
+            let services = DimseServicesConfig {
+                verification: mask & 1 != 0,
+                query: mask & 2 != 0,
+                storage: mask & 4 != 0,
+                retrieve: mask & 8 != 0,
+            };
+            let registries = build_dimse_service_registries(
+                Arc::clone(&ae_registry),
+                Some(Arc::clone(&ingest)),
+                Some(Arc::clone(&query)),
+                Some(Arc::clone(&retrieve)),
+                DimseServiceSelection::from_config(&services),
+            )
+            .expect("service registries");
+
+            let syntaxes = registries
+                .get("RUSTCOON_A")
+                .expect("registry")
+                .supported_abstract_syntax_uids();
+            let supports = |uid: &str| syntaxes.iter().any(|syntax| syntax == uid);
+            assert_eq!(
+                supports(VerificationServiceProvider::SOP_CLASS_UID),
+                services.verification,
+                "{services:?}"
+            );
+            assert_eq!(
+                supports(QueryServiceProvider::STUDY_ROOT_FIND_SOP_CLASS_UID),
+                services.query,
+                "{services:?}"
+            );
+            assert_eq!(
+                supports(StorageServiceProvider::DEFAULT_STORAGE_SOP_CLASS_UIDS[0]),
+                services.storage || services.retrieve,
+                "{services:?}"
+            );
+            assert_eq!(
+                supports(STUDY_ROOT_GET_SOP_CLASS_UID),
+                services.retrieve,
+                "{services:?}"
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(root);
+    }
 }
@@ -0,0 +1,670 @@
+use dicom_core::{DataElement, VR};
+use dicom_dictionary_std::{tags, uids};
+use rustcoon_config::ingest::ContentScannerConfig;
+use rustcoon_dicom::{SeriesInstanceUid, StudyInstanceUid};
+use rustcoon_dimse::StorageServiceProvider;
+use rustcoon_retrieve::{RetrieveLevel, RetrieveQueryModel, RetrieveRequest};
+
+mod common;
+use common::{CALLING_AE_TITLE, TestArchive, ct_instance, find_identifier, string};
+
+const STUDY_UID: &str = "1.2.826.0.1.3680043.10.1001.1";
+const SERIES_UID: &str = "1.2.826.0.1.3680043.10.1001.1.1";
+
+async fn archive_with_sample_study() -> Option<TestArchive> {
+    let archive = TestArchive::start().await;
+    for index in 1..=3 {
+        let instance = ct_instance(
+            "PAT-001",
+            STUDY_UID,
+            SERIES_UID,
+            &format!("{SERIES_UID}.{index}"),
+        );
+        assert_eq!(archive.store(&instance).await?, 0x0000);
+    }
+    let other = ct_instance(
+        "PAT-002",
+        "1.2.826.0.1.3680043.10.1002.1",
+        "1.2.826.0.1.3680043.10.1002.1.1",
+        "1.2.826.0.1.3680043.10.1002.1.1.1",
+    );
+    assert_eq!(archive.store(&other).await?, 0x0000);
+    Some(archive)
+}
+
+#[tokio::test]
+async fn stored_instances_are_found_at_every_query_level() {
+    let Some(archive) = archive_with_sample_study().await else {
+        return;
+    };
+
+    let (studies, status) = archive
+        .find(&find_identifier(
+            "STUDY",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, ""),
+                (tags::PATIENT_ID, VR::LO, "PAT-001"),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(studies.len(), 1);
+    assert_eq!(string(&studies[0], tags::STUDY_INSTANCE_UID), STUDY_UID);
+
+    let (series, status) = archive
+        .find(&find_identifier(
+            "SERIES",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, STUDY_UID),
+                (tags::SERIES_INSTANCE_UID, VR::UI, ""),
+                (tags::MODALITY, VR::CS, ""),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(series.len(), 1);
+    assert_eq!(string(&series[0], tags::MODALITY), "CT");
+
+    let (instances, status) = archive
+        .find(&find_identifier(
+            "IMAGE",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, STUDY_UID),
+                (tags::SERIES_INSTANCE_UID, VR::UI, SERIES_UID),
+                (tags::SOP_INSTANCE_UID, VR::UI, ""),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    let mut sop_instance_uids = instances
+        .iter()
+        .map(|instance| string(instance, tags::SOP_INSTANCE_UID))
+        .collect::<Vec<_>>();
+    sop_instance_uids.sort();
+    assert_eq!(
+        sop_instance_uids,
+        (1..=3)
+            .map(|index| format!("{SERIES_UID}.{index}"))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn stored_instances_round_trip_through_retrieve() {
+    let Some(archive) = archive_with_sample_study().await else {
+        return;
+    };
+
+    let data_sets = archive
+        .retrieve(
+            RetrieveRequest::new(RetrieveQueryModel::StudyRoot, RetrieveLevel::Series)
+                .with_study_instance_uid(StudyInstanceUid::new(STUDY_UID).expect("study uid"))
+                .with_series_instance_uid(SeriesInstanceUid::new(SERIES_UID).expect("series uid")),
+        )
+        .await;
+
+    assert_eq!(data_sets.len(), 3);
+    for data_set in &data_sets {
+        assert_eq!(string(data_set, tags::PATIENT_ID), "PAT-001");
+        assert_eq!(string(data_set, tags::SERIES_INSTANCE_UID), SERIES_UID);
+    }
+}
+
+#[tokio::test]
+async fn store_is_rejected_once_study_limit_is_reached() {
+    let archive = TestArchive::start_with(|config| {
+        config.ingest.max_instances_per_study = Some(1);
+    })
+    .await;
+
+    let first = ct_instance("PAT-001", STUDY_UID, SERIES_UID, "1.2.3.1");
+    let Some(status) = archive.store(&first).await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+    let second = ct_instance("PAT-001", STUDY_UID, SERIES_UID, "1.2.3.2");
+    assert_eq!(archive.store(&second).await, Some(0xA700));
+}
+
+#[tokio::test]
+async fn store_is_refused_when_content_scanner_rejects_payload() {
+    let archive = TestArchive::start_with(|config| {
+        config.ingest.content_scanner = Some(ContentScannerConfig {
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo 'test signature found'; exit 1".to_string(),
+            ],
+            ..ContentScannerConfig::default()
+        });
+    })
+    .await;
+
+    let instance = ct_instance("PAT-001", STUDY_UID, SERIES_UID, "1.2.3.1");
+    let Some(status) = archive.store(&instance).await else {
+        return;
+    };
+    assert_eq!(status, 0x0124);
+
+    let (studies, status) = archive
+        .find(&find_identifier(
+            "STUDY",
+            &[(tags::STUDY_INSTANCE_UID, VR::UI, STUDY_UID)],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert!(studies.is_empty());
+}
+
+#[tokio::test]
+async fn stored_instances_can_be_found_by_source_ae_title() {
+    let Some(archive) = archive_with_sample_study().await else {
+        return;
+    };
+    let receipt_keys = |source_ae_title| {
+        find_identifier(
+            "IMAGE",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, STUDY_UID),
+                (tags::SOP_INSTANCE_UID, VR::UI, ""),
+                (
+                    StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG,
+                    VR::LO,
+                    StorageServiceProvider::RECEIPT_PRIVATE_CREATOR,
+                ),
+                (
+                    StorageServiceProvider::SOURCE_AE_TITLE_TAG,
+                    VR::AE,
+                    source_ae_title,
+                ),
+                (StorageServiceProvider::RECEIVED_DATE_TIME_TAG, VR::DT, ""),
+            ],
+        )
+    };
+
+    let (instances, status) = archive
+        .find(&receipt_keys(CALLING_AE_TITLE))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(instances.len(), 3);
+    for instance in &instances {
+        assert_eq!(
+            string(instance, StorageServiceProvider::SOURCE_AE_TITLE_TAG),
+            CALLING_AE_TITLE
+        );
+        assert!(!string(instance, StorageServiceProvider::RECEIVED_DATE_TIME_TAG).is_empty());
+    }
+
+    let (instances, status) = archive
+        .find(&receipt_keys("CT_SCANNER_3"))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert!(instances.is_empty());
+}
+
+#[tokio::test]
+async fn source_ae_title_is_found_when_the_sender_used_the_receipt_block() {
+    let archive = TestArchive::start().await;
+    let mut instance = ct_instance("PAT-001", STUDY_UID, SERIES_UID, "1.2.3.1");
+    instance.put(DataElement::new(
+        StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG,
+        VR::LO,
+        "VENDOR",
+    ));
+    instance.put(DataElement::new(
+        StorageServiceProvider::SOURCE_AE_TITLE_TAG,
+        VR::AE,
+        "VENDOR_VALUE",
+    ));
+    let Some(status) = archive.store(&instance).await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+
+    let (instances, status) = archive
+        .find(&find_identifier(
+            "IMAGE",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, STUDY_UID),
+                (tags::SOP_INSTANCE_UID, VR::UI, ""),
+                (
+                    StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG,
+                    VR::LO,
+                    StorageServiceProvider::RECEIPT_PRIVATE_CREATOR,
+                ),
+                (
+                    StorageServiceProvider::SOURCE_AE_TITLE_TAG,
+                    VR::AE,
+                    CALLING_AE_TITLE,
+                ),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(instances.len(), 1);
+    assert_eq!(string(&instances[0], tags::SOP_INSTANCE_UID), "1.2.3.1");
+}
+
+#[tokio::test]
+async fn patient_ids_are_matched_within_their_issuer() {
+    let archive = TestArchive::start().await;
+    for (issuer, study_uid) in [
+        ("HOSPITAL_A", "1.2.826.0.1.3680043.10.1003.1"),
+        ("HOSPITAL_B", "1.2.826.0.1.3680043.10.1004.1"),
+    ] {
+        let mut instance = ct_instance(
+            "PAT-001",
+            study_uid,
+            &format!("{study_uid}.1"),
+            &format!("{study_uid}.1.1"),
+        );
+        instance.put(DataElement::new(tags::ISSUER_OF_PATIENT_ID, VR::LO, issuer));
+        let Some(status) = archive.store(&instance).await else {
+            return;
+        };
+        assert_eq!(status, 0x0000);
+    }
+
+    let (studies, status) = archive
+        .find(&find_identifier(
+            "STUDY",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, ""),
+                (tags::PATIENT_ID, VR::LO, "PAT-001"),
+                (tags::ISSUER_OF_PATIENT_ID, VR::LO, "HOSPITAL_B"),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(studies.len(), 1);
+    assert_eq!(
+        string(&studies[0], tags::STUDY_INSTANCE_UID),
+        "1.2.826.0.1.3680043.10.1004.1"
+    );
+    assert_eq!(
+        string(&studies[0], tags::ISSUER_OF_PATIENT_ID),
+        "HOSPITAL_B"
+    );
+}
+
+#[tokio::test]
+async fn modalities_in_study_track_current_series() {
+    const STUDY: &str = "1.2.826.0.1.3680043.10.1005.1";
+    let archive = TestArchive::start().await;
+    let store_as = async |series: &str, modality: &str| {
+        let mut instance = ct_instance(
+            "PAT-005",
+            STUDY,
+            &format!("{STUDY}.{series}"),
+            &format!("{STUDY}.{series}.1"),
+        );
+        instance.put(DataElement::new(tags::MODALITY, VR::CS, modality));
+        archive.store(&instance).await
+    };
+    let modalities_matching = async |key: &str| {
+        let (studies, status) = archive
+            .find(&find_identifier(
+                "STUDY",
+                &[
+                    (tags::STUDY_INSTANCE_UID, VR::UI, STUDY),
+                    (tags::MODALITIES_IN_STUDY, VR::CS, key),
+                ],
+            ))
+            .await
+            .expect("C-FIND");
+        assert_eq!(status, 0x0000);
+        studies
+            .first()
+            .map(|study| string(study, tags::MODALITIES_IN_STUDY))
+    };
+
+    let Some(status) = store_as("1", "CT").await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+    assert_eq!(store_as("2", "SR").await, Some(0x0000));
+    assert_eq!(store_as("3", "CT").await, Some(0x0000));
+
+    assert_eq!(modalities_matching("").await.as_deref(), Some("CT\\SR"));
+    assert_eq!(modalities_matching("SR").await.as_deref(), Some("CT\\SR"));
+    assert_eq!(modalities_matching("MR").await, None);
+
+    assert_eq!(store_as("2", "MR").await, Some(0x0000));
+    assert_eq!(modalities_matching("").await.as_deref(), Some("CT\\MR"));
+    assert_eq!(modalities_matching("SR").await, None);
+}
+
+#[tokio::test]
+async fn sop_classes_in_study_are_derived_from_stored_instances() {
+    const STUDY: &str = "1.2.826.0.1.3680043.10.1006.1";
+    let archive = TestArchive::start().await;
+    let sop_classes_matching = async |key: &str| {
+        let (studies, status) = archive
+            .find(&find_identifier(
+                "STUDY",
+                &[
+                    (tags::STUDY_INSTANCE_UID, VR::UI, STUDY),
+                    (tags::SOP_CLASSES_IN_STUDY, VR::UI, key),
+                ],
+            ))
+            .await
+            .expect("C-FIND");
+        assert_eq!(status, 0x0000);
+        studies
+            .first()
+            .map(|study| string(study, tags::SOP_CLASSES_IN_STUDY))
+    };
+
+    let instance = ct_instance(
+        "PAT-006",
+        STUDY,
+        &format!("{STUDY}.1"),
+        &format!("{STUDY}.1.1"),
+    );
+    let Some(status) = archive.store(&instance).await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+
+    assert_eq!(
+        sop_classes_matching("").await.as_deref(),
+        Some(uids::CT_IMAGE_STORAGE)
+    );
+    assert_eq!(
+        sop_classes_matching(uids::CT_IMAGE_STORAGE)
+            .await
+            .as_deref(),
+        Some(uids::CT_IMAGE_STORAGE)
+    );
+    assert_eq!(sop_classes_matching(uids::MR_IMAGE_STORAGE).await, None);
+}
+
+#[tokio::test]
+async fn series_are_matched_by_body_part_and_laterality() {
+    const STUDY: &str = "1.2.826.0.1.3680043.10.1007.1";
+    let archive = TestArchive::start().await;
+    let store_as = async |series: &str, body_part: &str, laterality: Option<&str>| {
+        let mut instance = ct_instance(
+            "PAT-007",
+            STUDY,
+            &format!("{STUDY}.{series}"),
+            &format!("{STUDY}.{series}.1"),
+        );
+        instance.put(DataElement::new(
+            tags::BODY_PART_EXAMINED,
+            VR::CS,
+            body_part,
+        ));
+        if let Some(laterality) = laterality {
+            instance.put(DataElement::new(tags::LATERALITY, VR::CS, laterality));
+        }
+        archive.store(&instance).await
+    };
+    let series_matching = async |body_part: &str, laterality: &str| {
+        let (series, status) = archive
+            .find(&find_identifier(
+                "SERIES",
+                &[
+                    (tags::STUDY_INSTANCE_UID, VR::UI, STUDY),
+                    (tags::SERIES_INSTANCE_UID, VR::UI, ""),
+                    (tags::BODY_PART_EXAMINED, VR::CS, body_part),
+                    (tags::LATERALITY, VR::CS, laterality),
+                ],
+            ))
+            .await
+            .expect("C-FIND");
+        assert_eq!(status, 0x0000);
+        let mut series = series
+            .iter()
+            .map(|series| {
+                format!(
+                    "{}:{}",
+                    string(series, tags::BODY_PART_EXAMINED),
+                    string(series, tags::LATERALITY)
+                )
+            })
+            .collect::<Vec<_>>();
+        series.sort();
+        series
+    };
+
+    let Some(status) = store_as("1", "CHEST", None).await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+    assert_eq!(store_as("2", "KNEE", Some("L")).await, Some(0x0000));
+    assert_eq!(store_as("3", "KNEE", Some("R")).await, Some(0x0000));
+
+    assert_eq!(
+        series_matching("", "").await,
+        ["CHEST:", "KNEE:L", "KNEE:R"]
+    );
+    assert_eq!(series_matching("CH*", "").await, ["CHEST:"]);
+    assert_eq!(series_matching("KNEE", "L").await, ["KNEE:L"]);
+    assert!(series_matching("", "B").await.is_empty());
+}
+
+#[tokio::test]
+async fn wildcard_matching_treats_like_characters_literally() {
+    const STUDY: &str = "1.2.826.0.1.3680043.10.1008";
+    let archive = TestArchive::start().await;
+    let store_for = async |index: u32, patient_id: &str| {
+        archive
+            .store(&ct_instance(
+                patient_id,
+                &format!("{STUDY}.{index}"),
+                &format!("{STUDY}.{index}.1"),
+                &format!("{STUDY}.{index}.1.1"),
+            ))
+            .await
+    };
+
+    let Some(status) = store_for(1, "WILD_008").await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+    assert_eq!(store_for(2, "WILDX008").await, Some(0x0000));
+
+    let (studies, status) = archive
+        .find(&find_identifier(
+            "STUDY",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, ""),
+                (tags::PATIENT_ID, VR::LO, "WILD_*"),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(studies.len(), 1);
+    assert_eq!(string(&studies[0], tags::PATIENT_ID), "WILD_008");
+}
+
+#[tokio::test]
+async fn person_names_match_regardless_of_accents_and_case() {
+    const STUDY: &str = "1.2.826.0.1.3680043.10.1007.2";
+    let archive = TestArchive::start().await;
+    let store_as = async |index: u32, patient_name: &str, referring_physician_name: &str| {
+        let study = format!("{STUDY}.{index}");
+        let mut instance = ct_instance(
+            "PAT-008",
+            &study,
+            &format!("{study}.1"),
+            &format!("{study}.1.1"),
+        );
+        instance.put(DataElement::new(
+            tags::SPECIFIC_CHARACTER_SET,
+            VR::CS,
+            "ISO_IR 192",
+        ));
+        instance.put(DataElement::new(tags::PATIENT_NAME, VR::PN, patient_name));
+        instance.put(DataElement::new(
+            tags::REFERRING_PHYSICIAN_NAME,
+            VR::PN,
+            referring_physician_name,
+        ));
+        archive.store(&instance).await
+    };
+    let studies_matching = async |key: (dicom_core::Tag, &str)| {
+        let mut identifier = find_identifier(
+            "STUDY",
+            &[
+                (tags::PATIENT_ID, VR::LO, "PAT-008"),
+                (tags::PATIENT_NAME, VR::PN, ""),
+                (tags::REFERRING_PHYSICIAN_NAME, VR::PN, ""),
+            ],
+        );
+        identifier.put(DataElement::new(
+            tags::SPECIFIC_CHARACTER_SET,
+            VR::CS,
+            "ISO_IR 192",
+        ));
+        identifier.put(DataElement::new(key.0, VR::PN, key.1));
+        let (studies, status) = archive.find(&identifier).await.expect("C-FIND");
+        assert_eq!(status, 0x0000);
+        let mut names = studies
+            .iter()
+            .map(|study| string(study, tags::PATIENT_NAME))
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    let Some(status) = store_as(1, "Müller^Hans", "Schäfer^Eva").await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+    assert_eq!(
+        store_as(2, "MULLER^Anna", "Schafer^Eva").await,
+        Some(0x0000)
+    );
+    assert_eq!(store_as(3, "Mahler^Gustav", "Other").await, Some(0x0000));
+
+    assert_eq!(
+        studies_matching((tags::PATIENT_NAME, "MULLER*")).await,
+        ["MULLER^Anna", "Müller^Hans"]
+    );
+    assert_eq!(
+        studies_matching((tags::PATIENT_NAME, "müller^hans")).await,
+        ["Müller^Hans"]
+    );
+    assert_eq!(
+        studies_matching((tags::REFERRING_PHYSICIAN_NAME, "SCHAFER^EVA")).await,
+        ["MULLER^Anna", "Müller^Hans"]
+    );
+}
+
+#[tokio::test]
+async fn store_rejects_moving_series_or_instances_to_another_study() {
+    const OTHER_STUDY: &str = "1.2.826.0.1.3680043.10.1007.3";
+    let archive = TestArchive::start().await;
+
+    let original = ct_instance("PAT-001", STUDY_UID, SERIES_UID, "1.2.3.1");
+    let Some(status) = archive.store(&original).await else {
+        return;
+    };
+    assert_eq!(status, 0x0000);
+
+    let series_collision = ct_instance("PAT-001", OTHER_STUDY, SERIES_UID, "1.2.3.2");
+    assert_eq!(archive.store(&series_collision).await, Some(0x0110));
+    let instance_collision = ct_instance(
+        "PAT-001",
+        OTHER_STUDY,
+        &format!("{OTHER_STUDY}.1"),
+        "1.2.3.1",
+    );
+    assert_eq!(archive.store(&instance_collision).await, Some(0x0110));
+
+    let stored = archive
+        .retrieve(
+            RetrieveRequest::new(RetrieveQueryModel::StudyRoot, RetrieveLevel::Series)
+                .with_study_instance_uid(StudyInstanceUid::new(STUDY_UID).expect("study uid"))
+                .with_series_instance_uid(SeriesInstanceUid::new(SERIES_UID).expect("series uid")),
+        )
+        .await;
+    assert_eq!(stored.len(), 1);
+    for tag in [
+        tags::STUDY_INSTANCE_UID,
+        tags::SERIES_INSTANCE_UID,
+        tags::SOP_INSTANCE_UID,
+    ] {
+        assert_eq!(string(&stored[0], tag), string(&original, tag));
+    }
+
+    let (series, status) = archive
+        .find(&find_identifier(
+            "SERIES",
+            &[
+                (tags::STUDY_INSTANCE_UID, VR::UI, ""),
+                (tags::SERIES_INSTANCE_UID, VR::UI, SERIES_UID),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert_eq!(series.len(), 1);
+    assert_eq!(string(&series[0], tags::STUDY_INSTANCE_UID), STUDY_UID);
+
+    let (studies, status) = archive
+        .find(&find_identifier(
+            "STUDY",
+            &[(tags::STUDY_INSTANCE_UID, VR::UI, OTHER_STUDY)],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    assert!(studies.is_empty());
+}
+
+#[tokio::test]
+async fn exclusion_matching_keeps_studies_without_the_attribute() {
+    const STUDY: &str = "1.2.826.0.1.3680043.10.1014.1";
+    let archive = TestArchive::start_with(|config| config.query.exclusion_matching = true).await;
+    for (index, description) in [(1, Some("CHEST")), (2, Some("HEAD")), (3, None)] {
+        let study = format!("{STUDY}.{index}");
+        let mut instance = ct_instance(
+            "PAT-014",
+            &study,
+            &format!("{study}.1"),
+            &format!("{study}.1.1"),
+        );
+        if let Some(description) = description {
+            instance.put(DataElement::new(
+                tags::STUDY_DESCRIPTION,
+                VR::LO,
+                description,
+            ));
+        }
+        let Some(status) = archive.store(&instance).await else {
+            return;
+        };
+        assert_eq!(status, 0x0000);
+    }
+
+    let (studies, status) = archive
+        .find(&find_identifier(
+            "STUDY",
+            &[
+                (tags::PATIENT_ID, VR::LO, "PAT-014"),
+                (tags::STUDY_DESCRIPTION, VR::LO, "!CHEST"),
+                (tags::STUDY_INSTANCE_UID, VR::UI, ""),
+            ],
+        ))
+        .await
+        .expect("C-FIND");
+    assert_eq!(status, 0x0000);
+    let mut uids = studies
+        .iter()
+        .map(|study| string(study, tags::STUDY_INSTANCE_UID))
+        .collect::<Vec<_>>();
+    uids.sort();
+    assert_eq!(uids, [format!("{STUDY}.2"), format!("{STUDY}.3")]);
+}
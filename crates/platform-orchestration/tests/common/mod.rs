@@ -0,0 +1,368 @@
+//! End-to-end archive harness for integration tests.
+//!
+//! Builds the monolith wiring against a temporary SQLite catalog and
+//! filesystem blob store, then drives DIMSE requests through the same
+//! service registry a listener would dispatch to.
+
+#![allow(dead_code)]
+
+use std::io::{Cursor, ErrorKind};
+use std::sync::Arc;
+use std::time::Duration;
+
+use dicom_core::{DataElement, PrimitiveValue, VR};
+use dicom_dictionary_std::{tags, uids};
+use dicom_encoding::transfer_syntax::TransferSyntaxIndex;
+use dicom_object::InMemDicomObject;
+use dicom_transfer_syntax_registry::TransferSyntaxRegistry;
+use dicom_ul::pdu::{PDataValue, PDataValueType};
+use rustcoon_application_entity::ApplicationEntityRegistry;
+use rustcoon_config::MonolithConfig;
+use rustcoon_config::application_entity::{
+    LocalApplicationEntityConfig, RemoteApplicationEntityConfig,
+};
+use rustcoon_dimse::{
+    AssociationContext, DimseCommand, DimseError, DimseListener, DimseReader, DimseWriter,
+    ServiceClassProvider, ServiceClassRegistry,
+};
+use rustcoon_orchestration::{
+    DimseServiceSelection, build_blob_store, build_catalog_ports, build_dimse_service_registries,
+    build_ingest_service, build_query_service, build_retrieve_service,
+};
+use rustcoon_retrieve::RetrieveService;
+use rustcoon_ul::{OutboundAssociationRequest, UlAssociation};
+use tempfile::TempDir;
+use tokio::io::AsyncReadExt;
+
+pub const LOCAL_AE_TITLE: &str = "RUSTCOON";
+pub const CALLING_AE_TITLE: &str = "HARNESS_SCU";
+
+/// A fully wired archive backed by a temporary directory.
+pub struct TestArchive {
+    ae_registry: Arc<ApplicationEntityRegistry>,
+    services: Arc<ServiceClassRegistry>,
+    retrieve: Arc<RetrieveService>,
+    _root: TempDir,
+}
+
+impl TestArchive {
+    /// Builds the archive with every DIMSE service class enabled.
+    pub async fn start() -> Self {
+        Self::start_with(|_| {}).await
+    }
+
+    /// Builds the archive after letting the caller adjust the configuration.
+    pub async fn start_with(configure: impl FnOnce(&mut MonolithConfig)) -> Self {
+        let root = TempDir::new().expect("temporary archive root");
+        let mut config = MonolithConfig::default();
+        config.filesystem.root = root.path().to_path_buf();
+        config.application_entities.local = vec![LocalApplicationEntityConfig {
+            title: LOCAL_AE_TITLE.to_string(),
+            bind_address: "127.0.0.1:0".parse().expect("valid addr"),
+            read_timeout_seconds: Some(5),
+            write_timeout_seconds: Some(5),
+            max_pdu_length: 16_384,
+            max_concurrent_associations: 8,
+        }];
+        config.application_entities.remote = vec![RemoteApplicationEntityConfig {
+            title: CALLING_AE_TITLE.to_string(),
+            address: "127.0.0.1:11112".parse().expect("valid addr"),
+            connect_timeout_seconds: Some(5),
+            read_timeout_seconds: Some(5),
+            write_timeout_seconds: Some(5),
+            max_pdu_length: 16_384,
+        }];
+        configure(&mut config);
+
+        let ae_registry = Arc::new(
+            ApplicationEntityRegistry::try_from_config(&config.application_entities)
+                .expect("valid AE registry"),
+        );
+        let blob_store = build_blob_store(&config);
+        let catalog_ports = build_catalog_ports(&config).await.expect("catalog ports");
+        let ingest = build_ingest_service(blob_store.clone(), &catalog_ports, &config.ingest)
+            .expect("ingest service");
+        let query = build_query_service(&catalog_ports, &config.query).expect("query service");
+        let retrieve = build_retrieve_service(blob_store, &catalog_ports);
+        let mut registries = build_dimse_service_registries(
+            Arc::clone(&ae_registry),
+            Some(ingest),
+            Some(query),
+            Some(Arc::clone(&retrieve)),
+            DimseServiceSelection::from_config(&config.runtime.dimse.services),
+        )
+        .expect("service registries");
+
+        Self {
+            ae_registry,
+            services: registries
+                .remove(LOCAL_AE_TITLE)
+                .expect("local AE registry"),
+            retrieve,
+            _root: root,
+        }
+    }
+
+    /// Sends one C-STORE-RQ and returns the response status.
+    ///
+    /// Returns `None` when the sandbox does not allow binding a local socket.
+    pub async fn store(&self, data_set: &InMemDicomObject) -> Option<u16> {
+        let sop_class_uid = string(data_set, tags::SOP_CLASS_UID);
+        let sop_instance_uid = string(data_set, tags::SOP_INSTANCE_UID);
+        let mut command = request_command(0x0001, &sop_class_uid);
+        command.put(DataElement::new(
+            tags::AFFECTED_SOP_INSTANCE_UID,
+            VR::UI,
+            sop_instance_uid,
+        ));
+
+        let responses = self.exchange(&sop_class_uid, &command, data_set).await?;
+        Some(responses.last().expect("C-STORE-RSP").0)
+    }
+
+    /// Sends one Study Root C-FIND-RQ and returns the pending identifiers
+    /// together with the final status.
+    pub async fn find(
+        &self,
+        identifier: &InMemDicomObject,
+    ) -> Option<(Vec<InMemDicomObject>, u16)> {
+        let sop_class_uid = uids::STUDY_ROOT_QUERY_RETRIEVE_INFORMATION_MODEL_FIND;
+        let command = request_command(0x0020, sop_class_uid);
+
+        let mut responses = self.exchange(sop_class_uid, &command, identifier).await?;
+        let (status, _) = responses.pop().expect("final C-FIND-RSP");
+        let matches = responses
+            .into_iter()
+            .filter_map(|(_, identifier)| identifier)
+            .collect();
+        Some((matches, status))
+    }
+
+    /// Reads the stored data set of every instance in the retrieve plan.
+    pub async fn retrieve(
+        &self,
+        request: rustcoon_retrieve::RetrieveRequest,
+    ) -> Vec<InMemDicomObject> {
+        let plan = self.retrieve.plan(request).await.expect("retrieve plan");
+        let mut data_sets = Vec::with_capacity(plan.instances.len());
+        for candidate in &plan.instances {
+            let mut bytes = Vec::new();
+            self.retrieve
+                .open(candidate)
+                .await
+                .expect("open stored instance")
+                .read_to_end(&mut bytes)
+                .await
+                .expect("read stored instance");
+            let transfer_syntax_uid = candidate
+                .transfer_syntax_uid
+                .as_ref()
+                .map_or(uids::IMPLICIT_VR_LITTLE_ENDIAN, |uid| uid.as_str());
+            data_sets.push(decode(transfer_syntax_uid, bytes));
+        }
+        data_sets
+    }
+
+    async fn exchange(
+        &self,
+        abstract_syntax_uid: &str,
+        command: &InMemDicomObject,
+        data_set: &InMemDicomObject,
+    ) -> Option<Vec<(u16, Option<InMemDicomObject>)>> {
+        let (mut server, mut client) = self.connect(abstract_syntax_uid).await?;
+        let services = Arc::clone(&self.services);
+        let handler = tokio::spawn(async move { services.handle(&mut server).await });
+
+        let context_id = client.presentation_contexts()[0].id;
+        let transfer_syntax_uid = client.presentation_contexts()[0].transfer_syntax.clone();
+        let mut writer = DimseWriter::new();
+        writer
+            .send_command_object(&mut client, context_id, command)
+            .await
+            .expect("send request command");
+        writer
+            .send_data_pdv(
+                &mut client,
+                PDataValue {
+                    presentation_context_id: context_id,
+                    value_type: PDataValueType::Data,
+                    is_last: true,
+                    data: encode(&transfer_syntax_uid, data_set),
+                },
+            )
+            .await
+            .expect("send request data set");
+
+        let mut reader = DimseReader::new();
+        let mut responses = Vec::new();
+        loop {
+            let response = reader
+                .read_command_object(&mut client)
+                .await
+                .expect("read response command");
+            let response = DimseCommand::from_command_object(&response).expect("parse response");
+            let status = response.status.expect("response status");
+            let data_set = if response.has_data_set {
+                let mut bytes = Vec::new();
+                while let Some(pdv) = reader
+                    .read_data_pdv(&mut client)
+                    .await
+                    .expect("read response data set")
+                {
+                    bytes.extend_from_slice(&pdv.data);
+                }
+                Some(decode(&transfer_syntax_uid, bytes))
+            } else {
+                None
+            };
+            responses.push((status, data_set));
+            if !matches!(status, 0xFF00 | 0xFF01) {
+                break;
+            }
+        }
+
+        handler
+            .await
+            .expect("service task")
+            .expect("service handles request");
+        Some(responses)
+    }
+
+    async fn connect(
+        &self,
+        abstract_syntax_uid: &str,
+    ) -> Option<(AssociationContext, UlAssociation)> {
+        let listener =
+            match DimseListener::bind_from_registry(Arc::clone(&self.ae_registry), LOCAL_AE_TITLE)
+                .await
+            {
+                Ok(listener) => listener.with_abstract_syntax(abstract_syntax_uid),
+                Err(DimseError::Ul(rustcoon_ul::UlError::Io(error)))
+                    if error.kind() == ErrorKind::PermissionDenied =>
+                {
+                    return None;
+                }
+                Err(error) => panic!("listener should bind: {error}"),
+            };
+        let addr = listener
+            .local_addr()
+            .expect("listener address should resolve");
+        let server = tokio::spawn(async move { listener.accept().await.expect("server accept").0 });
+
+        let client = OutboundAssociationRequest::new(CALLING_AE_TITLE, LOCAL_AE_TITLE, addr)
+            .connect_timeout(Duration::from_secs(5))
+            .read_timeout(Duration::from_secs(5))
+            .write_timeout(Duration::from_secs(5))
+            .max_pdu_length(16_384)
+            .with_abstract_syntax(abstract_syntax_uid)
+            .establish()
+            .await
+            .expect("client should establish");
+        let server = server.await.expect("server join");
+        Some((server, client))
+    }
+}
+
+/// Builds a minimal CT instance fixture.
+pub fn ct_instance(
+    patient_id: &str,
+    study_instance_uid: &str,
+    series_instance_uid: &str,
+    sop_instance_uid: &str,
+) -> InMemDicomObject {
+    let mut data_set = InMemDicomObject::new_empty();
+    data_set.put(DataElement::new(
+        tags::SOP_CLASS_UID,
+        VR::UI,
+        uids::CT_IMAGE_STORAGE,
+    ));
+    data_set.put(DataElement::new(
+        tags::SOP_INSTANCE_UID,
+        VR::UI,
+        sop_instance_uid,
+    ));
+    data_set.put(DataElement::new(
+        tags::STUDY_INSTANCE_UID,
+        VR::UI,
+        study_instance_uid,
+    ));
+    data_set.put(DataElement::new(
+        tags::SERIES_INSTANCE_UID,
+        VR::UI,
+        series_instance_uid,
+    ));
+    data_set.put(DataElement::new(tags::PATIENT_ID, VR::LO, patient_id));
+    data_set.put(DataElement::new(tags::PATIENT_NAME, VR::PN, "Doe^Jane"));
+    data_set.put(DataElement::new(tags::STUDY_DATE, VR::DA, "20260411"));
+    data_set.put(DataElement::new(tags::MODALITY, VR::CS, "CT"));
+    data_set
+}
+
+/// Builds a Study Root C-FIND identifier for the given level.
+pub fn find_identifier(level: &str, keys: &[(dicom_core::Tag, VR, &str)]) -> InMemDicomObject {
+    let mut identifier = InMemDicomObject::new_empty();
+    identifier.put(DataElement::new(tags::QUERY_RETRIEVE_LEVEL, VR::CS, level));
+    for (tag, vr, value) in keys {
+        identifier.put(DataElement::new(*tag, *vr, PrimitiveValue::from(*value)));
+    }
+    identifier
+}
+
+/// Reads a string element, panicking when it is absent.
+pub fn string(data_set: &InMemDicomObject, tag: dicom_core::Tag) -> String {
+    data_set
+        .element(tag)
+        .expect("element present")
+        .to_str()
+        .expect("string value")
+        .to_string()
+}
+
+fn request_command(command_field: u16, affected_sop_class_uid: &str) -> InMemDicomObject {
+    let mut command = InMemDicomObject::new_empty();
+    command.put(DataElement::new(
+        tags::COMMAND_FIELD,
+        VR::US,
+        PrimitiveValue::from(command_field),
+    ));
+    command.put(DataElement::new(
+        tags::COMMAND_DATA_SET_TYPE,
+        VR::US,
+        PrimitiveValue::from(0x0000_u16),
+    ));
+    command.put(DataElement::new(
+        tags::MESSAGE_ID,
+        VR::US,
+        PrimitiveValue::from(1_u16),
+    ));
+    command.put(DataElement::new(
+        tags::PRIORITY,
+        VR::US,
+        PrimitiveValue::from(0_u16),
+    ));
+    command.put(DataElement::new(
+        tags::AFFECTED_SOP_CLASS_UID,
+        VR::UI,
+        affected_sop_class_uid,
+    ));
+    command
+}
+
+fn encode(transfer_syntax_uid: &str, data_set: &InMemDicomObject) -> Vec<u8> {
+    let transfer_syntax = TransferSyntaxRegistry
+        .get(transfer_syntax_uid)
+        .expect("transfer syntax");
+    let mut bytes = Vec::new();
+    data_set
+        .write_dataset_with_ts(&mut bytes, transfer_syntax)
+        .expect("serialize data set");
+    bytes
+}
+
+fn decode(transfer_syntax_uid: &str, bytes: Vec<u8>) -> InMemDicomObject {
+    let transfer_syntax = TransferSyntaxRegistry
+        .get(transfer_syntax_uid)
+        .expect("transfer syntax");
+    InMemDicomObject::read_dataset_with_ts(Cursor::new(bytes), transfer_syntax)
+        .expect("decode data set")
+}
@@ -1,4 +1,8 @@
+use dicom_core::Tag;
+use dicom_core::header::Header;
+use dicom_dictionary_std::tags;
 use dicom_object::InMemDicomObject;
+use rustcoon_dicom::{normalize_date_time_utc, trim_value_padding};
 
 /// Extracted/indexable DICOM metadata document.
 ///
@@ -9,3 +13,140 @@
 /// only, so implementations should not require fully loading an entire DICOM
 /// object into memory in order to produce it.
 pub type DicomAttributeDocument = InMemDicomObject;
+
+/// Private creator of the archive receipt block. Receipt elements describe a
+/// delivery rather than the instance, so catalogs leave them out when deciding
+/// whether a re-store changed anything.
+pub const RECEIPT_PRIVATE_CREATOR: &str = "RUSTCOON RECEIPT";
+/// Group holding the receipt block.
+pub const RECEIPT_GROUP: u16 = 0x0009;
+
+/// Private block (the `xx` of `(0009,00xx)`) whose creator is the receipt
+/// creator, if the document carries a receipt.
+pub fn receipt_block(attributes: &DicomAttributeDocument) -> Option<u16> {
+    (0x0010..=0x00FF).find(|&block| {
+        promoted_text(attributes, Tag(RECEIPT_GROUP, block)).as_deref()
+            == Some(RECEIPT_PRIVATE_CREATOR)
+    })
+}
+
+/// Copy of the document without its receipt block.
+pub fn without_receipt(attributes: &DicomAttributeDocument) -> DicomAttributeDocument {
+    let mut stripped = attributes.clone();
+    if let Some(block) = receipt_block(attributes) {
+        let receipt = attributes
+            .iter()
+            .map(|element| element.tag())
+            .filter(|tag| {
+                tag.group() == RECEIPT_GROUP
+                    && (tag.element() == block || tag.element() >> 8 == block)
+            })
+            .collect::<Vec<_>>();
+        for tag in receipt {
+            stripped.remove_element(tag);
+        }
+    }
+    stripped
+}
+
+/// Reads a text attribute for a promoted column, treating empty values as absent.
+pub fn promoted_text(attributes: &DicomAttributeDocument, tag: Tag) -> Option<String> {
+    attributes
+        .element(tag)
+        .ok()
+        .and_then(|element| element.to_str().ok())
+        .map(|value| trim_value_padding(&value).to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// UTC study timestamp (`YYYYMMDDHHMMSS`) derived from Study Date, Study Time
+/// and Timezone Offset From UTC, as kept in the catalog's study table.
+pub fn study_date_time_utc(attributes: &DicomAttributeDocument) -> Option<String> {
+    let value = |tag| {
+        attributes
+            .element(tag)
+            .ok()
+            .and_then(|element| element.to_str().ok())
+            .map(|value| value.into_owned())
+    };
+    normalize_date_time_utc(
+        &value(tags::STUDY_DATE)?,
+        value(tags::STUDY_TIME).as_deref(),
+        value(tags::TIMEZONE_OFFSET_FROM_UTC).as_deref(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use dicom_core::{DataElement, Tag, VR};
+    use dicom_dictionary_std::tags;
+    use dicom_object::InMemDicomObject;
+
+    use super::{
+        RECEIPT_PRIVATE_CREATOR, promoted_text, receipt_block, study_date_time_utc, without_receipt,
+    };
+
+    #[test]
+    fn study_date_time_utc_combines_study_date_time_and_offset() {
+        let mut attributes = InMemDicomObject::new_empty();
+        assert_eq!(study_date_time_utc(&attributes), None);
+
+        attributes.put(DataElement::new(tags::STUDY_DATE, VR::DA, "20260411"));
+        attributes.put(DataElement::new(tags::STUDY_TIME, VR::TM, "233000"));
+        attributes.put(DataElement::new(
+            tags::TIMEZONE_OFFSET_FROM_UTC,
+            VR::SH,
+            "-0500",
+        ));
+
+        assert_eq!(
+            study_date_time_utc(&attributes).as_deref(),
+            Some("20260412043000")
+        );
+    }
+
+    #[test]
+    fn promoted_text_drops_padding_and_empty_values() {
+        let mut attributes = InMemDicomObject::new_empty();
+        assert_eq!(promoted_text(&attributes, tags::ISSUER_OF_PATIENT_ID), None);
+
+        attributes.put(DataElement::new(
+            tags::ISSUER_OF_PATIENT_ID,
+            VR::LO,
+            "HOSPITAL_A ",
+        ));
+        attributes.put(DataElement::new(tags::LATERALITY, VR::CS, " "));
+
+        assert_eq!(
+            promoted_text(&attributes, tags::ISSUER_OF_PATIENT_ID).as_deref(),
+            Some("HOSPITAL_A")
+        );
+        assert_eq!(promoted_text(&attributes, tags::LATERALITY), None);
+    }
+
+    #[test]
+    fn without_receipt_drops_only_the_receipt_block() {
+        let mut attributes = InMemDicomObject::new_empty();
+        attributes.put(DataElement::new(tags::PATIENT_ID, VR::LO, "PAT-001"));
+        attributes.put(DataElement::new(Tag(0x0009, 0x0010), VR::LO, "VENDOR"));
+        attributes.put(DataElement::new(Tag(0x0009, 0x1001), VR::LO, "vendor"));
+        assert_eq!(receipt_block(&attributes), None);
+        assert_eq!(without_receipt(&attributes), attributes);
+
+        let mut stamped = attributes.clone();
+        stamped.put(DataElement::new(
+            Tag(0x0009, 0x0011),
+            VR::LO,
+            format!("{RECEIPT_PRIVATE_CREATOR} "),
+        ));
+        stamped.put(DataElement::new(Tag(0x0009, 0x1101), VR::AE, "CT_SCANNER"));
+        stamped.put(DataElement::new(
+            Tag(0x0009, 0x1102),
+            VR::DT,
+            "20260411101530.500000+0000",
+        ));
+
+        assert_eq!(receipt_block(&stamped), Some(0x0011));
+        assert_eq!(without_receipt(&stamped), attributes);
+    }
+}
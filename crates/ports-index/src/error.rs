@@ -9,7 +9,9 @@ pub enum IndexOperation {
     GetSeries,
     GetInstance,
     Query,
+    ListSupersededBlobs,
     UpsertInstance,
+    PreviewUpsert,
     AttachBlob,
 }
 
@@ -33,6 +35,14 @@ pub enum IndexError {
         message: String,
     },
 
+    #[error("study {study_instance_uid} would exceed its {limit} limit ({current} of {max})")]
+    StudyQuotaExceeded {
+        study_instance_uid: String,
+        limit: &'static str,
+        current: u64,
+        max: u64,
+    },
+
     #[error("catalog backend unavailable")]
     Unavailable {
         transient: bool,
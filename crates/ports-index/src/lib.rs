@@ -14,7 +14,10 @@
 mod write;
 
 pub use attribute_path::{AttributePath, AttributePathSegment, ItemSelector};
-pub use document::DicomAttributeDocument;
+pub use document::{
+    DicomAttributeDocument, RECEIPT_GROUP, RECEIPT_PRIVATE_CREATOR, promoted_text, receipt_block,
+    study_date_time_utc, without_receipt,
+};
 pub use error::{IndexError, IndexOperation};
 pub use predicate::{MatchingRule, Predicate, RangeMatching, SequenceMatching};
 pub use query::{
@@ -25,4 +28,7 @@
     CatalogInstanceEntry, CatalogQueryEntry, CatalogReadStore, CatalogSeriesEntry,
     CatalogStudyEntry, StoredObjectRef,
 };
-pub use write::{CatalogStore, CatalogUpsertOutcome, CatalogWriteStore, InstanceUpsertRequest};
+pub use write::{
+    CatalogStore, CatalogUpsertOutcome, CatalogWriteStore, InstanceUpsertRequest, StudyQuota,
+    check_hierarchy_unchanged,
+};
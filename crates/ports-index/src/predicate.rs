@@ -7,6 +7,8 @@ pub enum Predicate {
     Any(Vec<Predicate>),
     Not(Box<Predicate>),
     Attribute(AttributePath, MatchingRule),
+    /// Matches the index-maintained UTC study timestamp (`YYYYMMDDHHMMSS`).
+    StudyDateTimeUtc(RangeMatching),
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +56,29 @@ pub fn until(end: impl Into<String>) -> Self {
     }
 }
 
+impl MatchingRule {
+    /// Applies `map` to every value the rule compares against; sequence
+    /// matching is returned unchanged.
+    #[must_use]
+    pub fn map_values(&self, map: impl Fn(&str) -> String) -> Self {
+        let range = |range: &RangeMatching| RangeMatching {
+            start: range.start.as_deref().map(&map),
+            end: range.end.as_deref().map(&map),
+        };
+        match self {
+            Self::SingleValue(value) => Self::SingleValue(map(value)),
+            Self::Wildcard(value) => Self::Wildcard(map(value)),
+            Self::UidList(values) => Self::UidList(values.iter().map(|value| map(value)).collect()),
+            Self::MultipleValues(values) => {
+                Self::MultipleValues(values.iter().map(|value| map(value)).collect())
+            }
+            Self::Range(value) => Self::Range(range(value)),
+            Self::DateTimeRange(value) => Self::DateTimeRange(range(value)),
+            Self::Universal | Self::EmptyValue | Self::Sequence(_) => self.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SequenceMatching {
     pub item: ItemSelector,
@@ -74,6 +99,7 @@ pub fn validate(&self) -> Result<(), AttributePathError> {
                 sequence.predicate.validate()?;
             }
             Self::Attribute(path, _) => path.validate()?,
+            Self::StudyDateTimeUtc(_) => {}
         }
 
         Ok(())
@@ -113,6 +139,27 @@ fn range_helpers_build_expected_shapes() {
         );
     }
 
+    #[test]
+    fn map_values_rewrites_compared_values_only() {
+        let upper = |value: &str| value.to_uppercase();
+        assert!(matches!(
+            MatchingRule::Wildcard("mul*".to_string()).map_values(upper),
+            MatchingRule::Wildcard(value) if value == "MUL*"
+        ));
+        assert!(matches!(
+            MatchingRule::MultipleValues(vec!["a".to_string(), "b".to_string()]).map_values(upper),
+            MatchingRule::MultipleValues(values) if values == ["A", "B"]
+        ));
+        assert!(matches!(
+            MatchingRule::Range(RangeMatching::from("a")).map_values(upper),
+            MatchingRule::Range(RangeMatching { start: Some(start), end: None }) if start == "A"
+        ));
+        assert!(matches!(
+            MatchingRule::EmptyValue.map_values(upper),
+            MatchingRule::EmptyValue
+        ));
+    }
+
     #[test]
     fn complex_predicate_tree_can_be_built() {
         let predicate = Predicate::All(vec![
@@ -152,4 +199,14 @@ fn datetime_range_predicate_variant_validates_like_other_attribute_rules() {
 
         predicate.validate().expect("predicate should validate");
     }
+
+    #[test]
+    fn study_date_time_utc_predicate_validates_without_attribute_path() {
+        let predicate = Predicate::All(vec![Predicate::StudyDateTimeUtc(RangeMatching::closed(
+            "20260411000000",
+            "20260411235959",
+        ))]);
+
+        predicate.validate().expect("predicate should validate");
+    }
 }
@@ -38,6 +38,8 @@ pub fn with_size_bytes(mut self, size_bytes: u64) -> Self {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CatalogStudyEntry {
     pub record: DicomStudyRecord,
+    pub instance_count: u64,
+    pub size_bytes: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -75,6 +77,13 @@ async fn get_instance(
     ) -> Result<Option<CatalogInstanceEntry>, IndexError>;
 
     async fn query(&self, query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, IndexError>;
+
+    /// Blobs the instance referenced before it was re-stored under another
+    /// key, newest first.
+    async fn list_superseded_blobs(
+        &self,
+        sop_instance_uid: &SopInstanceUid,
+    ) -> Result<Vec<StoredObjectRef>, IndexError>;
 }
 
 #[cfg(test)]
@@ -8,6 +8,44 @@ pub struct InstanceUpsertRequest {
     pub record: DicomInstanceRecord,
     pub attributes: DicomAttributeDocument,
     pub blob: Option<StoredObjectRef>,
+    pub study_quota: Option<StudyQuota>,
+}
+
+/// Study totals an upsert may not push past. Adapters check them against the
+/// counters updated in the upsert's own transaction, so concurrent writes to
+/// one study cannot overshoot together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StudyQuota {
+    pub max_instances: Option<u64>,
+    pub max_size_bytes: Option<u64>,
+}
+
+impl StudyQuota {
+    /// Rejects totals that grew past a limit. `deltas` are the instance-count
+    /// and size changes the write applied to reach `totals`.
+    pub fn check(
+        &self,
+        study_instance_uid: &str,
+        (instance_count, size_bytes): (u64, u64),
+        (instance_delta, size_delta): (i64, i64),
+    ) -> Result<(), IndexError> {
+        let exceeded = |limit, total: u64, delta: i64, max: Option<u64>| match max {
+            Some(max) if delta > 0 && total > max => Err(IndexError::StudyQuotaExceeded {
+                study_instance_uid: study_instance_uid.to_string(),
+                limit,
+                current: total.saturating_sub(delta as u64),
+                max,
+            }),
+            _ => Ok(()),
+        };
+        exceeded(
+            "instance count",
+            instance_count,
+            instance_delta,
+            self.max_instances,
+        )?;
+        exceeded("size", size_bytes, size_delta, self.max_size_bytes)
+    }
 }
 
 impl InstanceUpsertRequest {
@@ -16,6 +54,7 @@ pub fn new(record: DicomInstanceRecord) -> Self {
             record,
             attributes: DicomAttributeDocument::new_empty(),
             blob: None,
+            study_quota: None,
         }
     }
 
@@ -28,6 +67,11 @@ pub fn with_blob(mut self, blob: StoredObjectRef) -> Self {
         self.blob = Some(blob);
         self
     }
+
+    pub fn with_study_quota(mut self, study_quota: StudyQuota) -> Self {
+        self.study_quota = Some(study_quota);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,6 +88,13 @@ async fn upsert_instance(
         request: InstanceUpsertRequest,
     ) -> Result<CatalogUpsertOutcome, IndexError>;
 
+    /// Reports the outcome `upsert_instance` would have for `request`,
+    /// including hierarchy conflicts, without writing anything.
+    async fn preview_upsert(
+        &self,
+        request: &InstanceUpsertRequest,
+    ) -> Result<CatalogUpsertOutcome, IndexError>;
+
     async fn attach_blob(
         &self,
         identity: &DicomInstanceIdentity,
@@ -51,6 +102,38 @@ async fn attach_blob(
     ) -> Result<(), IndexError>;
 }
 
+/// Rejects writes that would move an existing series or instance to a
+/// different parent. Adapters pass the study currently recorded for the
+/// series and the study/series currently recorded for the instance.
+pub fn check_hierarchy_unchanged(
+    identity: &DicomInstanceIdentity,
+    existing_series_study: Option<&str>,
+    existing_instance_parents: Option<(&str, &str)>,
+) -> Result<(), IndexError> {
+    let study_instance_uid = identity.study_instance_uid().as_str();
+    if let Some(study) = existing_series_study
+        && study != study_instance_uid
+    {
+        return Err(IndexError::conflict(
+            identity.sop_instance_uid().clone(),
+            format!(
+                "series {} already belongs to study {study}",
+                identity.series_instance_uid().as_str()
+            ),
+        ));
+    }
+    if let Some((study, series)) = existing_instance_parents
+        && (study != study_instance_uid || series != identity.series_instance_uid().as_str())
+    {
+        return Err(IndexError::conflict(
+            identity.sop_instance_uid().clone(),
+            format!("instance already belongs to series {series} of study {study}"),
+        ));
+    }
+
+    Ok(())
+}
+
 pub trait CatalogStore: CatalogReadStore + CatalogWriteStore + Send + Sync {}
 
 impl<T> CatalogStore for T where T: CatalogReadStore + CatalogWriteStore + Send + Sync {}
@@ -74,8 +157,8 @@ mod tests {
     };
     use crate::{
         CatalogReadStore, CatalogStore, CatalogUpsertOutcome, CatalogWriteStore,
-        InstanceUpsertRequest, Page, Paging, QueryRetrieveScope, StoredObjectRef,
-        StudyRootQueryRetrieveLevel,
+        InstanceUpsertRequest, Page, Paging, QueryRetrieveScope, StoredObjectRef, StudyQuota,
+        StudyRootQueryRetrieveLevel, check_hierarchy_unchanged,
     };
 
     struct MockCatalogStore;
@@ -110,6 +193,13 @@ async fn query(&self, _query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, I
                 Some(0),
             ))
         }
+
+        async fn list_superseded_blobs(
+            &self,
+            _sop_instance_uid: &SopInstanceUid,
+        ) -> Result<Vec<StoredObjectRef>, IndexError> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -121,6 +211,13 @@ async fn upsert_instance(
             Ok(CatalogUpsertOutcome::Created)
         }
 
+        async fn preview_upsert(
+            &self,
+            _request: &InstanceUpsertRequest,
+        ) -> Result<CatalogUpsertOutcome, IndexError> {
+            Ok(CatalogUpsertOutcome::Created)
+        }
+
         async fn attach_blob(
             &self,
             _identity: &DicomInstanceIdentity,
@@ -176,6 +273,26 @@ fn catalog_store_marker_trait_accepts_combined_store() {
         assert_catalog_store(&MockCatalogStore);
     }
 
+    #[test]
+    fn hierarchy_check_rejects_reparented_series_and_instances() {
+        let identity = sample_record().identity().clone();
+
+        assert!(check_hierarchy_unchanged(&identity, None, None).is_ok());
+        assert!(
+            check_hierarchy_unchanged(&identity, Some("1.2.3"), Some(("1.2.3", "1.2.3.1"))).is_ok()
+        );
+        assert!(matches!(
+            check_hierarchy_unchanged(&identity, Some("1.2.9"), None),
+            Err(IndexError::Conflict { message, .. })
+                if message == "series 1.2.3.1 already belongs to study 1.2.9"
+        ));
+        assert!(matches!(
+            check_hierarchy_unchanged(&identity, Some("1.2.3"), Some(("1.2.3", "1.2.3.9"))),
+            Err(IndexError::Conflict { message, .. })
+                if message == "instance already belongs to series 1.2.3.9 of study 1.2.3"
+        ));
+    }
+
     #[tokio::test]
     async fn mock_catalog_store_traits_are_exercised() {
         let store = MockCatalogStore;
@@ -230,4 +347,33 @@ async fn mock_catalog_store_traits_are_exercised() {
             Some(0)
         );
     }
+
+    #[test]
+    fn study_quota_rejects_only_growth_past_a_limit() {
+        let quota = StudyQuota {
+            max_instances: Some(2),
+            max_size_bytes: Some(1_000),
+        };
+
+        assert!(quota.check("1.2.3", (2, 1_000), (1, 500)).is_ok());
+        assert!(quota.check("1.2.3", (3, 1_200), (0, -100)).is_ok());
+        assert!(matches!(
+            quota.check("1.2.3", (3, 900), (1, 300)),
+            Err(IndexError::StudyQuotaExceeded {
+                limit: "instance count",
+                current: 2,
+                max: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            quota.check("1.2.3", (2, 1_001), (0, 1)),
+            Err(IndexError::StudyQuotaExceeded {
+                limit: "size",
+                current: 1_000,
+                max: 1_000,
+                ..
+            })
+        ));
+    }
 }
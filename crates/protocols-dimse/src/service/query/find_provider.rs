@@ -373,7 +373,7 @@ mod tests {
     use rustcoon_dicom::{SeriesInstanceUid, SopInstanceUid, StudyInstanceUid};
     use rustcoon_index::{
         CatalogInstanceEntry, CatalogQuery, CatalogQueryEntry, CatalogReadStore,
-        CatalogSeriesEntry, CatalogStudyEntry, IndexError, Page, Paging,
+        CatalogSeriesEntry, CatalogStudyEntry, IndexError, Page, Paging, StoredObjectRef,
     };
     use rustcoon_query::QueryService;
 
@@ -412,6 +412,13 @@ async fn query(&self, _query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, I
                 Some(0),
             ))
         }
+
+        async fn list_superseded_blobs(
+            &self,
+            _sop_instance_uid: &SopInstanceUid,
+        ) -> Result<Vec<StoredObjectRef>, IndexError> {
+            Ok(Vec::new())
+        }
     }
 
     #[test]
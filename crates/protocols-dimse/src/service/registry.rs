@@ -14,6 +14,7 @@
 #[derive(Default)]
 pub struct ServiceClassRegistry {
     providers: HashMap<(CommandField, String), Arc<dyn ServiceClassProvider>>,
+    accepted_only: Vec<String>,
 }
 
 impl ServiceClassRegistry {
@@ -48,11 +49,24 @@ pub fn register_described<P>(&mut self, provider: Arc<P>) -> &mut Self
         self
     }
 
+    /// Accepts abstract syntaxes without routing commands for them, e.g. the
+    /// storage SOP classes C-GET sub-operations are sent on.
+    pub fn accept_abstract_syntaxes<I, S>(&mut self, sop_class_uids: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.accepted_only
+            .extend(sop_class_uids.into_iter().map(Into::into));
+        self
+    }
+
     pub fn supported_abstract_syntax_uids(&self) -> Vec<String> {
         let mut values = self
             .providers
             .keys()
             .map(|(_, uid)| uid.as_str())
+            .chain(self.accepted_only.iter().map(String::as_str))
             .filter(|uid| *uid != ANY_SOP_CLASS_UID)
             .map(str::to_string)
             .collect::<Vec<_>>();
@@ -329,6 +343,22 @@ fn supported_abstract_syntax_uids_are_sorted_unique_and_skip_wildcard() {
         );
     }
 
+    #[test]
+    fn accepted_only_abstract_syntaxes_are_listed_without_a_provider() {
+        let mut registry = ServiceClassRegistry::new();
+        registry.accept_abstract_syntaxes(["1.2.840.10008.5.1.4.1.1.2", "*"]);
+
+        assert_eq!(
+            registry.supported_abstract_syntax_uids(),
+            vec!["1.2.840.10008.5.1.4.1.1.2".to_string()]
+        );
+        assert!(
+            registry
+                .provider_for(CommandField::CStoreRq, Some("1.2.840.10008.5.1.4.1.1.2"))
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn handle_dispatches_to_exact_registered_provider() {
         let Some((server_association, mut client_association, context_id)) =
@@ -5,6 +5,7 @@
 use dicom_dictionary_std::tags;
 use dicom_encoding::transfer_syntax::TransferSyntaxIndex;
 use dicom_object::InMemDicomObject;
+use dicom_object::meta::FileMetaTable;
 use dicom_transfer_syntax_registry::TransferSyntaxRegistry;
 use dicom_ul::pdu::{PDataValue, PDataValueType};
 use rustcoon_dicom::{SeriesInstanceUid, SopInstanceUid, StudyInstanceUid};
@@ -221,9 +222,24 @@ pub(crate) async fn send_store_sub_operation(
     move_originator: Option<(&str, u16)>,
 ) -> Result<StoreSubOperationStatus, DimseError> {
     let presentation_context_id = store_presentation_context_id(ctx, candidate)?;
-    let payload = match read_retrieve_payload(retrieve, candidate).await {
+    let transfer_syntax_uid = negotiated_transfer_syntax_uid(
+        ctx,
+        presentation_context_id,
+        candidate.identity.sop_class_uid().as_str(),
+    )?;
+    let payload = match read_retrieve_payload(retrieve, candidate)
+        .await
+        .and_then(|payload| data_set_payload(payload, &transfer_syntax_uid))
+    {
         Ok(payload) => payload,
-        Err(_) => return Ok(StoreSubOperationStatus::Failed),
+        Err(error) => {
+            tracing::warn!(
+                sop_instance_uid = candidate.identity.sop_instance_uid().as_str(),
+                error = %error,
+                "failed to prepare stored instance for C-STORE sub-operation"
+            );
+            return Ok(StoreSubOperationStatus::Failed);
+        }
     };
     let command = c_store_rq_command(candidate, message_id, move_originator);
 
@@ -284,6 +300,33 @@ async fn read_retrieve_payload(
     Ok(payload)
 }
 
+/// Returns the bare data set of a stored payload.
+///
+/// Ingest stores data sets without a preamble or file meta group, but blobs
+/// written as Part 10 files are accepted too: their meta group is dropped after
+/// checking that it declares the negotiated transfer syntax.
+fn data_set_payload(payload: Vec<u8>, transfer_syntax_uid: &str) -> Result<Vec<u8>, DimseError> {
+    let meta_start = if payload.get(128..132) == Some(b"DICM".as_slice()) {
+        128
+    } else if payload.starts_with(b"DICM") {
+        0
+    } else {
+        return Ok(payload);
+    };
+
+    let mut reader = Cursor::new(&payload[meta_start..]);
+    let meta = FileMetaTable::from_reader(&mut reader)
+        .map_err(|err| DimseError::protocol(format!("invalid file meta group: {err}")))?;
+    let stored_transfer_syntax_uid = meta.transfer_syntax();
+    if stored_transfer_syntax_uid != transfer_syntax_uid {
+        return Err(DimseError::protocol(format!(
+            "stored transfer syntax {stored_transfer_syntax_uid} does not match negotiated {transfer_syntax_uid}"
+        )));
+    }
+    let data_set_start = meta_start + reader.position() as usize;
+    Ok(payload[data_set_start..].to_vec())
+}
+
 fn c_store_rq_command(
     candidate: &RetrieveInstanceCandidate,
     message_id: u16,
@@ -355,3 +398,57 @@ fn c_store_rsp_status(
         None => Err(DimseError::protocol("missing Status in C-STORE-RSP")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dicom_dictionary_std::uids;
+    use dicom_object::meta::FileMetaTableBuilder;
+
+    use super::data_set_payload;
+
+    fn part10_file(transfer_syntax_uid: &str, data_set: &[u8], preamble: bool) -> Vec<u8> {
+        let meta = FileMetaTableBuilder::new()
+            .media_storage_sop_class_uid(uids::CT_IMAGE_STORAGE)
+            .media_storage_sop_instance_uid("1.2.3.4")
+            .transfer_syntax(transfer_syntax_uid)
+            .build()
+            .expect("file meta");
+        let mut bytes = if preamble { vec![0; 128] } else { Vec::new() };
+        bytes.extend_from_slice(b"DICM");
+        meta.write(&mut bytes).expect("write file meta");
+        bytes.extend_from_slice(data_set);
+        bytes
+    }
+
+    #[test]
+    fn bare_data_sets_pass_through_unchanged() {
+        let payload = vec![0x08, 0x00, 0x16, 0x00, 0x02, 0x00, 0x00, 0x00];
+
+        assert_eq!(
+            data_set_payload(payload.clone(), uids::IMPLICIT_VR_LITTLE_ENDIAN).expect("payload"),
+            payload
+        );
+    }
+
+    #[test]
+    fn part10_payloads_drop_preamble_and_file_meta() {
+        let data_set = [0x08, 0x00, 0x16, 0x00, 0x02, 0x00, 0x00, 0x00];
+
+        for preamble in [true, false] {
+            let payload = part10_file(uids::EXPLICIT_VR_LITTLE_ENDIAN, &data_set, preamble);
+            assert_eq!(
+                data_set_payload(payload, uids::EXPLICIT_VR_LITTLE_ENDIAN).expect("payload"),
+                data_set
+            );
+        }
+    }
+
+    #[test]
+    fn part10_payloads_reject_mismatched_transfer_syntax() {
+        let payload = part10_file(uids::EXPLICIT_VR_LITTLE_ENDIAN, &[], true);
+
+        let error = data_set_payload(payload, uids::IMPLICIT_VR_LITTLE_ENDIAN)
+            .expect_err("transfer syntax mismatch");
+        assert!(error.to_string().contains("does not match negotiated"));
+    }
+}
@@ -91,6 +91,10 @@ pub fn from_command(command: &DimseCommand) -> Result<Self, DimseError> {
 pub enum CStoreStatus {
     /// 0x0000 - operation completed successfully.
     Success,
+    /// 0x0110 - the instance conflicts with what the archive already holds.
+    ProcessingFailure,
+    /// 0x0124 - the instance was refused by local policy, such as a content scanner.
+    NotAuthorized,
     /// 0xA700 - local resource exhaustion while receiving or persisting the instance.
     OutOfResources,
     /// 0xA900 - the received data set does not match the requested SOP Class.
@@ -103,6 +107,8 @@ impl CStoreStatus {
     pub fn code(self) -> u16 {
         match self {
             Self::Success => 0x0000,
+            Self::ProcessingFailure => 0x0110,
+            Self::NotAuthorized => 0x0124,
             Self::OutOfResources => 0xA700,
             Self::DataSetDoesNotMatchSopClass => 0xA900,
             Self::CannotUnderstand => 0xC000,
@@ -411,6 +417,8 @@ fn status_codes_match_expected_values() {
         assert_eq!(CStoreStatus::OutOfResources.code(), 0xA700);
         assert_eq!(CStoreStatus::DataSetDoesNotMatchSopClass.code(), 0xA900);
         assert_eq!(CStoreStatus::CannotUnderstand.code(), 0xC000);
+        assert_eq!(CStoreStatus::ProcessingFailure.code(), 0x0110);
+        assert_eq!(CStoreStatus::NotAuthorized.code(), 0x0124);
     }
 
     #[test]
@@ -3,7 +3,10 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use dicom_core::Tag;
+use dicom_core::chrono::{DateTime, Utc};
+use dicom_core::header::Header;
+use dicom_core::value::{PrimitiveValue, Value};
+use dicom_core::{DataElement, Tag, VR};
 use dicom_dictionary_std::{tags, uids};
 use dicom_encoding::transfer_syntax::TransferSyntaxIndex;
 use dicom_object::DicomCollectorOptions;
@@ -11,11 +14,13 @@
 use dicom_object::file::ReadPreamble;
 use dicom_transfer_syntax_registry::TransferSyntaxRegistry;
 use dicom_ul::pdu::PDataValue;
+use rustcoon_application_entity::AeTitle;
 use rustcoon_dicom::{
     DicomInstanceIdentity, DicomInstanceMetadata, DicomInstanceRecord, DicomPatient,
     DicomSeriesMetadata, DicomStudyMetadata, SeriesInstanceUid, SopClassUid, SopInstanceUid,
-    StudyInstanceUid, TransferSyntaxUid,
+    StudyInstanceUid, TransferSyntaxUid, trim_trailing_padding, trim_value_padding,
 };
+use rustcoon_index::{IndexError, RECEIPT_GROUP};
 use rustcoon_ingest::{IngestError, IngestRequest, IngestService};
 use tempfile::NamedTempFile;
 
@@ -66,6 +71,15 @@ impl StorageServiceProvider {
         uids::LEGACY_CONVERTED_ENHANCED_PET_IMAGE_STORAGE,
     ];
 
+    /// Private creator of the archive receipt block in catalog documents.
+    pub const RECEIPT_PRIVATE_CREATOR: &str = rustcoon_index::RECEIPT_PRIVATE_CREATOR;
+    /// Receipt block creator element; the block is reserved for the receipt.
+    pub const RECEIPT_PRIVATE_CREATOR_TAG: Tag = Tag(RECEIPT_GROUP, 0x00F0);
+    /// Calling AE title of the association that delivered the instance.
+    pub const SOURCE_AE_TITLE_TAG: Tag = Tag(RECEIPT_GROUP, 0xF001);
+    /// UTC date and time the archive received the instance.
+    pub const RECEIVED_DATE_TIME_TAG: Tag = Tag(RECEIPT_GROUP, 0xF002);
+
     pub fn new(
         ingest: Arc<IngestService>,
         sop_class_uids: impl IntoIterator<Item = impl Into<String>>,
@@ -89,40 +103,70 @@ impl ServiceClassProvider for StorageServiceProvider {
     async fn handle(&self, ctx: &mut AssociationContext) -> Result<(), DimseError> {
         let request = CStoreRequest::from_command(&ctx.read_command().await?)?;
         tracing::debug!(stage = "validate", "C-STORE request validated");
-        let failure = match receive_data_set_to_temp_file(ctx).await {
+        let max_size_bytes = self.ingest.max_instance_size_bytes();
+        let failure = match receive_data_set_to_temp_file(ctx, max_size_bytes).await {
             Ok(payload_file) => {
                 tracing::debug!(stage = "dataset_received", "C-STORE data set received");
                 match build_ingest_request(ctx, &request, payload_file.as_file()) {
-                    Ok(ingest_request) => match payload_file.reopen() {
-                        Ok(std_file) => {
-                            let mut reader = tokio::fs::File::from_std(std_file);
-                            tracing::debug!(
-                                stage = "backend_call",
-                                backend = "ingest",
-                                "C-STORE ingest started"
-                            );
-                            match self.ingest.ingest(ingest_request, &mut reader).await {
-                                Ok(_) => None,
-                                Err(error) => {
-                                    tracing::warn!(
-                                        stage = "backend_failure",
-                                        backend = "ingest",
-                                        error = %error,
-                                        "C-STORE ingest failed"
-                                    );
-                                    Some(map_ingest_error_status(&error))
+                    Ok(ingest_request) => match self
+                        .ingest
+                        .scan_payload(&ingest_request.record, payload_file.path())
+                        .await
+                    {
+                        Ok(()) => match payload_file.reopen() {
+                            Ok(std_file) => {
+                                let mut reader = tokio::fs::File::from_std(std_file);
+                                tracing::debug!(
+                                    stage = "backend_call",
+                                    backend = "ingest",
+                                    "C-STORE ingest started"
+                                );
+                                match self.ingest.ingest(ingest_request, &mut reader).await {
+                                    Ok(_) => None,
+                                    Err(error) => {
+                                        if let IngestError::StudyLimitExceeded {
+                                            study_instance_uid,
+                                            ..
+                                        } = &error
+                                        {
+                                            tracing::error!(
+                                                stage = "study_limit",
+                                                backend = "ingest",
+                                                study_instance_uid = %study_instance_uid,
+                                                error = %error,
+                                                "C-STORE rejected instance over study limit"
+                                            );
+                                        } else {
+                                            tracing::warn!(
+                                                stage = "backend_failure",
+                                                backend = "ingest",
+                                                error = %error,
+                                                "C-STORE ingest failed"
+                                            );
+                                        }
+                                        Some(map_ingest_error_status(&error))
+                                    }
                                 }
                             }
+                            Err(_) => Some(StoreFailure::out_of_resources(
+                                "failed to reopen temporary payload storage",
+                            )),
+                        },
+                        Err(error) => {
+                            tracing::warn!(
+                                stage = "content_scan",
+                                backend = "ingest",
+                                error = %error,
+                                "C-STORE payload failed content scan"
+                            );
+                            Some(map_ingest_error_status(&error))
                         }
-                        Err(_) => Some(StoreFailure::out_of_resources(
-                            "failed to reopen temporary payload storage",
-                        )),
                     },
                     Err(failure) => Some(failure),
                 }
             }
             Err(ReceiveDataSetError::Dimse(error)) => return Err(error),
-            Err(ReceiveDataSetError::Status(status)) => Some(StoreFailure::new(status)),
+            Err(ReceiveDataSetError::Failure(failure)) => Some(failure),
         };
 
         let response = if let Some(failure) = failure {
@@ -160,7 +204,7 @@ fn bindings(&self) -> &[ServiceBinding] {
 
 enum ReceiveDataSetError {
     Dimse(DimseError),
-    Status(CStoreStatus),
+    Failure(StoreFailure),
 }
 
 #[derive(Debug)]
@@ -208,23 +252,41 @@ async fn drain_remaining_data_set(ctx: &mut AssociationContext) -> Result<(), Di
 
 async fn receive_data_set_to_temp_file(
     ctx: &mut AssociationContext,
+    max_size_bytes: Option<u64>,
 ) -> Result<NamedTempFile, ReceiveDataSetError> {
     let mut file = match NamedTempFile::new() {
         Ok(file) => file,
         Err(_) => {
             drain_remaining_data_set(ctx).await?;
-            return Err(ReceiveDataSetError::Status(CStoreStatus::OutOfResources));
+            return Err(ReceiveDataSetError::Failure(StoreFailure::new(
+                CStoreStatus::OutOfResources,
+            )));
         }
     };
 
+    let mut received = 0_u64;
     while let Some(PDataValue { data, .. }) = ctx.read_data_pdv().await? {
+        received += data.len() as u64;
+        if let Some(max_size_bytes) = max_size_bytes.filter(|max| received > *max) {
+            // Keep reading so the association stays usable for the next message.
+            drain_remaining_data_set(ctx).await?;
+            return Err(ReceiveDataSetError::Failure(
+                StoreFailure::out_of_resources(format!(
+                    "data set exceeds the {max_size_bytes} byte instance limit"
+                )),
+            ));
+        }
         if file.write_all(&data).is_err() {
             drain_remaining_data_set(ctx).await?;
-            return Err(ReceiveDataSetError::Status(CStoreStatus::OutOfResources));
+            return Err(ReceiveDataSetError::Failure(StoreFailure::new(
+                CStoreStatus::OutOfResources,
+            )));
         }
     }
     if file.flush().is_err() {
-        return Err(ReceiveDataSetError::Status(CStoreStatus::OutOfResources));
+        return Err(ReceiveDataSetError::Failure(StoreFailure::new(
+            CStoreStatus::OutOfResources,
+        )));
     }
     Ok(file)
 }
@@ -279,6 +341,7 @@ fn build_ingest_request(
     collector
         .read_dataset_up_to_pixeldata(&mut data_set)
         .map_err(|_| StoreFailure::cannot_understand("failed to decode C-STORE data set"))?;
+    let data_set = trim_text_padding(&data_set);
 
     let data_set_sop_class_uid =
         required_string(&data_set, tags::SOP_CLASS_UID).map_err(|tag| {
@@ -380,15 +443,76 @@ fn build_ingest_request(
         ),
     );
 
+    let calling_ae_title = ctx
+        .route()
+        .and_then(|route| route.calling_ae_title.as_ref());
+    let data_set = stamp_receipt(data_set, calling_ae_title, Utc::now());
+
     Ok(IngestRequest::new(record).with_attributes(data_set))
 }
 
+/// Records where and when the instance was received in the archive's private
+/// receipt block. Only the catalog document is stamped; the stored payload is
+/// kept byte-for-byte as sent. The receipt always occupies (0009,00F0) so
+/// queries can address it by fixed tags: a block another creator placed there
+/// is evicted from the document, as is any earlier receipt in another block.
+fn stamp_receipt(
+    mut data_set: InMemDicomObject,
+    calling_ae_title: Option<&AeTitle>,
+    received_at: DateTime<Utc>,
+) -> InMemDicomObject {
+    let reserved = StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG.element();
+    let receipt_blocks = (0x0010..=0x00FF)
+        .filter(|&block| {
+            block == reserved
+                || data_set
+                    .element(Tag(RECEIPT_GROUP, block))
+                    .ok()
+                    .and_then(|element| element.to_str().ok())
+                    .is_some_and(|creator| {
+                        trim_value_padding(&creator)
+                            == StorageServiceProvider::RECEIPT_PRIVATE_CREATOR
+                    })
+        })
+        .collect::<Vec<_>>();
+    let stale = data_set
+        .iter()
+        .map(|element| element.tag())
+        .filter(|tag| {
+            tag.group() == RECEIPT_GROUP
+                && (receipt_blocks.contains(&tag.element())
+                    || receipt_blocks.contains(&(tag.element() >> 8)))
+        })
+        .collect::<Vec<_>>();
+    for tag in stale {
+        data_set.remove_element(tag);
+    }
+    data_set.put(DataElement::new(
+        StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG,
+        VR::LO,
+        StorageServiceProvider::RECEIPT_PRIVATE_CREATOR,
+    ));
+    if let Some(title) = calling_ae_title {
+        data_set.put(DataElement::new(
+            StorageServiceProvider::SOURCE_AE_TITLE_TAG,
+            VR::AE,
+            title.as_str(),
+        ));
+    }
+    data_set.put(DataElement::new(
+        StorageServiceProvider::RECEIVED_DATE_TIME_TAG,
+        VR::DT,
+        received_at.format("%Y%m%d%H%M%S%.6f+0000").to_string(),
+    ));
+    data_set
+}
+
 fn required_string(data_set: &InMemDicomObject, tag: Tag) -> Result<String, Tag> {
     data_set
         .element(tag)
         .map_err(|_| tag)?
         .to_str()
-        .map(|value| value.to_string())
+        .map(|value| trim_value_padding(&value).to_string())
         .map_err(|_| tag)
 }
 
@@ -400,10 +524,40 @@ fn optional_string(data_set: &InMemDicomObject, tag: Tag) -> Result<Option<Strin
 
     element
         .to_str()
-        .map(|value| Some(value.to_string()))
+        .map(|value| Some(trim_value_padding(&value).to_string()))
         .map_err(|_| tag)
 }
 
+/// Strips value padding from string elements so indexed values compare equal
+/// to trimmed query keys. Text VRs keep their significant leading spaces.
+fn trim_text_padding(data_set: &InMemDicomObject) -> InMemDicomObject {
+    InMemDicomObject::from_element_iter(data_set.iter().map(|element| {
+        let trim = match element.vr() {
+            VR::ST | VR::LT | VR::UT | VR::UC | VR::UR => trim_trailing_padding,
+            _ => trim_value_padding,
+        };
+        let value = match element.value() {
+            Value::Primitive(PrimitiveValue::Str(value)) => {
+                PrimitiveValue::Str(trim(value).to_string()).into()
+            }
+            Value::Primitive(PrimitiveValue::Strs(values)) => {
+                PrimitiveValue::Strs(values.iter().map(|value| trim(value).to_string()).collect())
+                    .into()
+            }
+            Value::Sequence(sequence) => Value::new_sequence(
+                sequence
+                    .items()
+                    .iter()
+                    .map(trim_text_padding)
+                    .collect::<Vec<_>>(),
+                sequence.length(),
+            ),
+            value => value.clone(),
+        };
+        DataElement::new(element.tag(), element.vr(), value)
+    }))
+}
+
 fn optional_u32(data_set: &InMemDicomObject, tag: Tag) -> Result<Option<u32>, Tag> {
     let element = match data_set.element(tag) {
         Ok(element) => element,
@@ -415,7 +569,33 @@ fn optional_u32(data_set: &InMemDicomObject, tag: Tag) -> Result<Option<u32>, Ta
 
 fn map_ingest_error_status(error: &IngestError) -> StoreFailure {
     match error {
-        IngestError::BeginWrite(_)
+        IngestError::StudyLimitExceeded { .. } => {
+            StoreFailure::out_of_resources("study instance or size limit reached")
+        }
+        IngestError::InstanceTooLarge { max_size_bytes } => StoreFailure::out_of_resources(
+            format!("data set exceeds the {max_size_bytes} byte instance limit"),
+        ),
+        // Not Authorized is the closest C-STORE refusal for a policy
+        // decision about the content itself.
+        IngestError::ContentRejected { reason } => {
+            let mut failure = StoreFailure::new(CStoreStatus::NotAuthorized);
+            failure.error_comment = Some(format!("rejected by content scanner: {reason}"));
+            failure
+        }
+        IngestError::ContentScan(_) => {
+            StoreFailure::out_of_resources("content scan could not be completed")
+        }
+        IngestError::CatalogUpdate {
+            source: IndexError::Conflict { message, .. },
+            ..
+        } => {
+            let mut failure = StoreFailure::new(CStoreStatus::ProcessingFailure);
+            failure.error_comment = Some(message.clone());
+            failure
+        }
+        IngestError::StudyLimitLookup(_)
+        | IngestError::ExistingInstanceLookup(_)
+        | IngestError::BeginWrite(_)
         | IngestError::CommitWrite(_)
         | IngestError::HeadBlob(_)
         | IngestError::CatalogUpdate { .. } => {
@@ -436,6 +616,8 @@ fn map_ingest_error_status(error: &IngestError) -> StoreFailure {
 fn store_status_error_class(status: CStoreStatus) -> DimseErrorClass {
     match status {
         CStoreStatus::Success => DimseErrorClass::new("service", "unknown"),
+        CStoreStatus::ProcessingFailure => DimseErrorClass::new("service", "conflict"),
+        CStoreStatus::NotAuthorized => DimseErrorClass::new("service", "not_authorized"),
         CStoreStatus::OutOfResources => DimseErrorClass::new("backend", "out_of_resources"),
         CStoreStatus::DataSetDoesNotMatchSopClass => {
             DimseErrorClass::new("service", "invalid_dataset")
@@ -454,7 +636,8 @@ mod tests {
     use std::time::Duration;
 
     use async_trait::async_trait;
-    use dicom_core::{DataElement, PrimitiveValue, VR};
+    use dicom_core::chrono::{DateTime, Utc};
+    use dicom_core::{DataElement, PrimitiveValue, Tag, VR};
     use dicom_dictionary_std::{tags, uids};
     use dicom_encoding::TransferSyntaxIndex;
     use dicom_object::InMemDicomObject;
@@ -464,6 +647,7 @@ mod tests {
     use rustcoon_config::application_entity::{
         ApplicationEntitiesConfig, LocalApplicationEntityConfig, RemoteApplicationEntityConfig,
     };
+    use rustcoon_index::receipt_block;
     use rustcoon_index::{
         CatalogInstanceEntry, CatalogQuery, CatalogQueryEntry, CatalogReadStore,
         CatalogSeriesEntry, CatalogStudyEntry, CatalogUpsertOutcome, CatalogWriteStore, IndexError,
@@ -481,7 +665,7 @@ mod tests {
     use super::{
         CStoreRequest, CStoreStatus, StorageServiceProvider, build_ingest_request,
         drain_remaining_data_set, map_ingest_error_status, optional_string, optional_u32,
-        required_string,
+        required_string, stamp_receipt,
     };
     use crate::service::{CommandField, DescribedServiceClassProvider, DimseCommand};
     use crate::{AssociationContext, DimseError, DimseReader, DimseWriter, ServiceClassProvider};
@@ -676,6 +860,13 @@ async fn query(&self, _query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, I
                 Some(0),
             ))
         }
+
+        async fn list_superseded_blobs(
+            &self,
+            _sop_instance_uid: &rustcoon_dicom::SopInstanceUid,
+        ) -> Result<Vec<StoredObjectRef>, IndexError> {
+            Ok(Vec::new())
+        }
     }
 
     #[async_trait]
@@ -692,6 +883,13 @@ async fn upsert_instance(
             Ok(CatalogUpsertOutcome::Created)
         }
 
+        async fn preview_upsert(
+            &self,
+            _request: &rustcoon_index::InstanceUpsertRequest,
+        ) -> Result<CatalogUpsertOutcome, IndexError> {
+            Ok(CatalogUpsertOutcome::Created)
+        }
+
         async fn attach_blob(
             &self,
             _identity: &rustcoon_dicom::DicomInstanceIdentity,
@@ -899,6 +1097,77 @@ async fn storage_provider_handles_store_and_returns_success_response() {
         );
     }
 
+    #[tokio::test]
+    async fn storage_provider_rejects_data_sets_over_instance_size_limit() {
+        let Some((server_association, mut client_association)) =
+            setup_ul_pair(uids::CT_IMAGE_STORAGE).await
+        else {
+            return;
+        };
+        let context_id = client_association.presentation_contexts()[0].id;
+
+        let state = Arc::new(Mutex::new(State::default()));
+        let storage: Arc<dyn BlobStore> = Arc::new(BlobStoreMock {
+            state: Arc::clone(&state),
+        });
+        let catalog = Arc::new(CatalogMock {
+            state: Arc::clone(&state),
+        });
+        let provider = StorageServiceProvider::new(
+            Arc::new(
+                IngestService::new(
+                    storage,
+                    catalog.clone(),
+                    catalog,
+                    Arc::new(HierarchicalInstanceKeyResolver::new()),
+                )
+                .with_max_instance_size_bytes(16),
+            ),
+            [uids::CT_IMAGE_STORAGE],
+        );
+
+        DimseWriter::new()
+            .send_command_object(&mut client_association, context_id, &c_store_rq_command())
+            .await
+            .expect("send C-STORE-RQ command");
+        let bytes = serialize_data_set(&client_association, context_id, &data_set());
+        DimseWriter::new()
+            .send_data_pdv(
+                &mut client_association,
+                PDataValue {
+                    presentation_context_id: context_id,
+                    value_type: PDataValueType::Data,
+                    is_last: true,
+                    data: bytes,
+                },
+            )
+            .await
+            .expect("send data set");
+
+        let mut server_context = AssociationContext::new(server_association);
+        provider
+            .handle(&mut server_context)
+            .await
+            .expect("handle C-STORE-RQ");
+
+        let response_object = DimseReader::new()
+            .read_command_object(&mut client_association)
+            .await
+            .expect("read C-STORE-RSP");
+        let response = DimseCommand::from_command_object(&response_object).expect("parse response");
+        assert_eq!(response.status, Some(0xA700));
+        assert_eq!(
+            response_object
+                .command
+                .element(tags::ERROR_COMMENT)
+                .expect("error comment")
+                .to_str()
+                .expect("error comment string"),
+            "data set exceeds the 16 byte instance limit"
+        );
+        assert!(state.lock().expect("state lock").requests.is_empty());
+    }
+
     #[test]
     fn store_request_parser_requires_dataset_and_priority() {
         let mut command = DimseCommand {
@@ -1146,6 +1415,103 @@ async fn storage_provider_rejects_abstract_syntax_mismatch_with_command() {
         );
     }
 
+    #[test]
+    fn stamp_receipt_records_source_in_the_reserved_block() {
+        let received_at = DateTime::parse_from_rfc3339("2026-04-11T10:15:30.5Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+
+        let stamped = stamp_receipt(
+            data_set(),
+            Some(&"CT_SCANNER_3".parse().expect("ae title")),
+            received_at,
+        );
+
+        let value = |tag| {
+            stamped
+                .element(tag)
+                .expect("element present")
+                .to_str()
+                .expect("string")
+                .to_string()
+        };
+        assert_eq!(
+            value(StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG),
+            "RUSTCOON RECEIPT"
+        );
+        assert_eq!(
+            value(StorageServiceProvider::SOURCE_AE_TITLE_TAG),
+            "CT_SCANNER_3"
+        );
+        assert_eq!(
+            value(StorageServiceProvider::RECEIVED_DATE_TIME_TAG),
+            "20260411101530.500000+0000"
+        );
+
+        let anonymous = stamp_receipt(stamped, None, received_at);
+        assert!(
+            anonymous
+                .element(StorageServiceProvider::SOURCE_AE_TITLE_TAG)
+                .is_err()
+        );
+        assert!(
+            anonymous
+                .element(StorageServiceProvider::RECEIVED_DATE_TIME_TAG)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn stamp_receipt_evicts_foreign_blocks_from_the_reserved_slot() {
+        let mut data_set = data_set();
+        data_set.put(DataElement::new(
+            StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG,
+            VR::LO,
+            "VENDOR",
+        ));
+        data_set.put(DataElement::new(Tag(0x0009, 0xF0AA), VR::LO, "vendor"));
+        data_set.put(DataElement::new(Tag(0x0009, 0x0010), VR::LO, "OTHER"));
+        data_set.put(DataElement::new(Tag(0x0009, 0x1001), VR::LO, "kept"));
+        data_set.put(DataElement::new(
+            Tag(0x0009, 0x0011),
+            VR::LO,
+            "RUSTCOON RECEIPT",
+        ));
+        data_set.put(DataElement::new(Tag(0x0009, 0x1101), VR::AE, "UPSTREAM"));
+        let received_at = DateTime::parse_from_rfc3339("2026-04-11T10:15:30.5Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+
+        let stamped = stamp_receipt(
+            data_set,
+            Some(&"CT_SCANNER_3".parse().expect("ae title")),
+            received_at,
+        );
+
+        let value = |tag| {
+            stamped
+                .element(tag)
+                .expect("element present")
+                .to_str()
+                .expect("string")
+                .to_string()
+        };
+        assert_eq!(
+            value(StorageServiceProvider::RECEIPT_PRIVATE_CREATOR_TAG),
+            "RUSTCOON RECEIPT"
+        );
+        assert_eq!(
+            value(StorageServiceProvider::SOURCE_AE_TITLE_TAG),
+            "CT_SCANNER_3"
+        );
+        assert!(stamped.element(Tag(0x0009, 0xF0AA)).is_err());
+        assert_eq!(value(Tag(0x0009, 0x0010)), "OTHER");
+        assert_eq!(value(Tag(0x0009, 0x1001)), "kept");
+        assert!(stamped.element(Tag(0x0009, 0x0011)).is_err());
+        assert!(stamped.element(Tag(0x0009, 0x1101)).is_err());
+        assert_eq!(receipt_block(&stamped), Some(0x00F0));
+    }
+
     #[tokio::test]
     async fn build_ingest_request_extracts_metadata_and_rejects_invalid_datasets() {
         let Some((server_association, client_association)) =
@@ -1204,6 +1570,50 @@ async fn build_ingest_request_extracts_metadata_and_rejects_invalid_datasets() {
             "PAT-001"
         );
 
+        let Some((server_association, client_association)) =
+            setup_ul_pair(uids::CT_IMAGE_STORAGE).await
+        else {
+            return;
+        };
+        let context_id = client_association.presentation_contexts()[0].id;
+        let request = store_request(context_id);
+        let mut padded_data_set = data_set();
+        padded_data_set.put(DataElement::new(
+            tags::STUDY_INSTANCE_UID,
+            VR::UI,
+            " 1.2.3\0",
+        ));
+        padded_data_set.put(DataElement::new(tags::STUDY_DESCRIPTION, VR::LO, " CHEST "));
+        padded_data_set.put(DataElement::new(
+            tags::IMAGE_COMMENTS,
+            VR::LT,
+            "  indented ",
+        ));
+        let payload = data_set_file(&client_association, context_id, &padded_data_set);
+        let server_context = AssociationContext::new(server_association);
+        let ingest_request = build_ingest_request(&server_context, &request, payload.as_file())
+            .expect("padded ingest request");
+        assert_eq!(
+            ingest_request
+                .record
+                .identity()
+                .study_instance_uid()
+                .as_str(),
+            "1.2.3"
+        );
+        let attribute = |tag| {
+            ingest_request
+                .attributes
+                .element(tag)
+                .expect("attribute")
+                .to_str()
+                .expect("string")
+                .into_owned()
+        };
+        assert_eq!(attribute(tags::STUDY_INSTANCE_UID), "1.2.3");
+        assert_eq!(attribute(tags::STUDY_DESCRIPTION), "CHEST");
+        assert_eq!(attribute(tags::IMAGE_COMMENTS), "  indented");
+
         let Some((server_association, client_association)) =
             setup_ul_pair(uids::CT_IMAGE_STORAGE).await
         else {
@@ -1298,6 +1708,16 @@ fn helper_functions_cover_validation_error_paths_and_status_mapping() {
             ),
             rollback_failed: None,
         };
+        let study_limit = IngestError::StudyLimitExceeded {
+            study_instance_uid: "1.2.3".to_string(),
+            limit: "instance count",
+            current: 10,
+            max: 10,
+        };
+        assert_eq!(
+            map_ingest_error_status(&study_limit).status,
+            CStoreStatus::OutOfResources
+        );
         assert_eq!(
             map_ingest_error_status(&begin_write).status,
             CStoreStatus::OutOfResources
@@ -1306,10 +1726,43 @@ fn helper_functions_cover_validation_error_paths_and_status_mapping() {
             map_ingest_error_status(&catalog_update).status,
             CStoreStatus::OutOfResources
         );
+        let reparented = map_ingest_error_status(&IngestError::CatalogUpdate {
+            source: IndexError::conflict(
+                rustcoon_dicom::SopInstanceUid::new("1.2.3.1.1").unwrap(),
+                "series 1.2.3.1 already belongs to study 1.2.4",
+            ),
+            rollback_failed: None,
+        });
+        assert_eq!(reparented.status, CStoreStatus::ProcessingFailure);
+        assert_eq!(
+            reparented.error_comment.as_deref(),
+            Some("series 1.2.3.1 already belongs to study 1.2.4")
+        );
         assert_eq!(
             map_ingest_error_status(&blob_key).status,
             CStoreStatus::OutOfResources
         );
+        assert_eq!(
+            map_ingest_error_status(&IngestError::InstanceTooLarge {
+                max_size_bytes: 1_024
+            })
+            .error_comment
+            .as_deref(),
+            Some("data set exceeds the 1024 byte instance limit")
+        );
+        let rejected = map_ingest_error_status(&IngestError::ContentRejected {
+            reason: "EICAR signature".to_string(),
+        });
+        assert_eq!(rejected.status, CStoreStatus::NotAuthorized);
+        assert_eq!(
+            rejected.error_comment.as_deref(),
+            Some("rejected by content scanner: EICAR signature")
+        );
+        assert_eq!(
+            map_ingest_error_status(&IngestError::ContentScan(std::io::Error::other("timeout")))
+                .status,
+            CStoreStatus::OutOfResources
+        );
     }
 
     #[tokio::test]
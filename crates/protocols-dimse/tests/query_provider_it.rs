@@ -15,7 +15,7 @@
 };
 use rustcoon_index::{
     CatalogInstanceEntry, CatalogQuery, CatalogQueryEntry, CatalogReadStore, CatalogSeriesEntry,
-    CatalogStudyEntry, IndexError, Page, Paging,
+    CatalogStudyEntry, IndexError, Page, Paging, StoredObjectRef,
 };
 use rustcoon_query::QueryService;
 
@@ -156,6 +156,13 @@ async fn query(&self, _query: CatalogQuery) -> Result<Page<CatalogQueryEntry>, I
             Some(1),
         ))
     }
+
+    async fn list_superseded_blobs(
+        &self,
+        _sop_instance_uid: &SopInstanceUid,
+    ) -> Result<Vec<StoredObjectRef>, IndexError> {
+        Ok(Vec::new())
+    }
 }
 
 #[tokio::test]
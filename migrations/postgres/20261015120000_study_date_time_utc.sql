@@ -0,0 +1,3 @@
+ALTER TABLE studies ADD COLUMN study_date_time_utc TEXT;
+
+CREATE INDEX idx_studies_study_date_time_utc ON studies (study_date_time_utc);
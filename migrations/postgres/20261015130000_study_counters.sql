@@ -0,0 +1,12 @@
+ALTER TABLE studies ADD COLUMN instance_count BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE studies ADD COLUMN size_bytes     BIGINT NOT NULL DEFAULT 0;
+
+UPDATE studies s
+SET instance_count = counts.instance_count,
+    size_bytes     = counts.size_bytes
+FROM (SELECT study_instance_uid,
+             COUNT(*)                            AS instance_count,
+             COALESCE(SUM(blob_size_bytes), 0)   AS size_bytes
+      FROM instances
+      GROUP BY study_instance_uid) counts
+WHERE counts.study_instance_uid = s.study_instance_uid;
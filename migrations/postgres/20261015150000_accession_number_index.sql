@@ -0,0 +1,2 @@
+-- text_pattern_ops serves both exact and prefix wildcard (LIKE 'ACC%') matching.
+CREATE INDEX idx_studies_accession_number ON studies (accession_number text_pattern_ops);
@@ -0,0 +1,21 @@
+ALTER TABLE studies ADD COLUMN referring_physician_name TEXT;
+
+-- Folded (unaccented, uppercase) companions used for matching and ordering.
+-- They are filled by the catalog store at write time and backfilled on
+-- startup so both backends fold names identically.
+ALTER TABLE studies ADD COLUMN patient_name_search TEXT;
+ALTER TABLE studies ADD COLUMN referring_physician_name_search TEXT;
+
+UPDATE studies
+SET referring_physician_name = (
+    SELECT NULLIF(TRIM(jsonb_extract_path_text(instances.attributes, 'tag', '00080090', 'Value', '0', 'Alphabetic')), '')
+    FROM instances
+    WHERE instances.study_instance_uid = studies.study_instance_uid
+    ORDER BY instances.updated_at DESC
+    LIMIT 1
+);
+
+CREATE INDEX idx_studies_patient_name_search
+    ON studies (patient_name_search text_pattern_ops);
+CREATE INDEX idx_studies_referring_physician_name_search
+    ON studies (referring_physician_name_search text_pattern_ops);
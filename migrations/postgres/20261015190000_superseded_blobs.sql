@@ -0,0 +1,18 @@
+-- Blobs an instance pointed at before it was re-stored under a new key. They
+-- stay counted in the study size until they are removed.
+CREATE TABLE superseded_blobs
+(
+    blob_key           TEXT PRIMARY KEY,
+    sop_instance_uid   TEXT        NOT NULL,
+    study_instance_uid TEXT        NOT NULL,
+    blob_version       TEXT,
+    blob_size_bytes    BIGINT,
+
+    superseded_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+    CONSTRAINT fk_superseded_blobs_instance
+        FOREIGN KEY (sop_instance_uid) REFERENCES instances (sop_instance_uid)
+);
+
+CREATE INDEX idx_superseded_blobs_sop_instance_uid
+    ON superseded_blobs (sop_instance_uid, superseded_at);
@@ -0,0 +1,7 @@
+-- One-off data backfills the catalog store has finished, so startup does not
+-- rescan rows the backfill could not fill.
+CREATE TABLE catalog_backfills
+(
+    name         TEXT PRIMARY KEY,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
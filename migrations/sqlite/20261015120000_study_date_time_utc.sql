@@ -0,0 +1,4 @@
+ALTER TABLE studies ADD COLUMN study_date_time_utc TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_studies_study_date_time_utc
+    ON studies (study_date_time_utc);
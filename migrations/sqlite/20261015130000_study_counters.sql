@@ -0,0 +1,15 @@
+ALTER TABLE studies ADD COLUMN instance_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE studies ADD COLUMN size_bytes INTEGER NOT NULL DEFAULT 0;
+
+UPDATE studies
+SET
+    instance_count = (
+        SELECT COUNT(*)
+        FROM instances
+        WHERE instances.study_instance_uid = studies.study_instance_uid
+    ),
+    size_bytes = (
+        SELECT COALESCE(SUM(blob_size_bytes), 0)
+        FROM instances
+        WHERE instances.study_instance_uid = studies.study_instance_uid
+    );
@@ -0,0 +1,13 @@
+ALTER TABLE studies ADD COLUMN issuer_of_patient_id TEXT;
+
+UPDATE studies
+SET issuer_of_patient_id = (
+    SELECT json_extract(instances.attributes, '$.tag."00100021".Value[0]')
+    FROM instances
+    WHERE instances.study_instance_uid = studies.study_instance_uid
+    ORDER BY instances.updated_at DESC
+    LIMIT 1
+);
+
+CREATE INDEX IF NOT EXISTS idx_studies_patient_id_issuer
+    ON studies (patient_id, issuer_of_patient_id);
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS idx_studies_accession_number
+    ON studies (accession_number);
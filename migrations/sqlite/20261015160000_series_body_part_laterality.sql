@@ -0,0 +1,21 @@
+ALTER TABLE series ADD COLUMN body_part_examined TEXT;
+ALTER TABLE series ADD COLUMN laterality TEXT;
+
+UPDATE series
+SET
+    body_part_examined = (
+        SELECT NULLIF(TRIM(json_extract(instances.attributes, '$.tag."00180015".Value[0]')), '')
+        FROM instances
+        WHERE instances.series_instance_uid = series.series_instance_uid
+        ORDER BY instances.updated_at DESC
+        LIMIT 1
+    ),
+    laterality = (
+        SELECT NULLIF(TRIM(json_extract(instances.attributes, '$.tag."00200060".Value[0]')), '')
+        FROM instances
+        WHERE instances.series_instance_uid = series.series_instance_uid
+        ORDER BY instances.updated_at DESC
+        LIMIT 1
+    );
+
+CREATE INDEX IF NOT EXISTS idx_series_body_part_examined ON series (body_part_examined);
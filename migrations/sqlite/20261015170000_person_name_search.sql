@@ -0,0 +1,21 @@
+ALTER TABLE studies ADD COLUMN referring_physician_name TEXT;
+
+-- Folded (unaccented, uppercase) companions used for matching and ordering.
+-- They are filled by the catalog store at write time and backfilled on
+-- startup, since SQLite cannot fold non-ASCII text itself.
+ALTER TABLE studies ADD COLUMN patient_name_search TEXT;
+ALTER TABLE studies ADD COLUMN referring_physician_name_search TEXT;
+
+UPDATE studies
+SET referring_physician_name = (
+    SELECT NULLIF(TRIM(json_extract(instances.attributes, '$.tag."00080090".Value[0].Alphabetic')), '')
+    FROM instances
+    WHERE instances.study_instance_uid = studies.study_instance_uid
+    ORDER BY instances.updated_at DESC
+    LIMIT 1
+);
+
+CREATE INDEX IF NOT EXISTS idx_studies_patient_name_search
+    ON studies (patient_name_search);
+CREATE INDEX IF NOT EXISTS idx_studies_referring_physician_name_search
+    ON studies (referring_physician_name_search);
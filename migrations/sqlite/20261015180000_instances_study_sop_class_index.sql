@@ -0,0 +1,4 @@
+-- Serves the SOP Classes in Study aggregate, which collects the distinct SOP
+-- classes of each study's instances from the index alone.
+CREATE INDEX IF NOT EXISTS idx_instances_study_sop_class_uid
+    ON instances (study_instance_uid, sop_class_uid);
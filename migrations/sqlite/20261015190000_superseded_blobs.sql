@@ -0,0 +1,17 @@
+-- Blobs an instance pointed at before it was re-stored under a new key. They
+-- stay counted in the study size until they are removed.
+CREATE TABLE IF NOT EXISTS superseded_blobs
+(
+    blob_key TEXT PRIMARY KEY,
+    sop_instance_uid TEXT NOT NULL,
+    study_instance_uid TEXT NOT NULL,
+    blob_version TEXT,
+    blob_size_bytes INTEGER,
+
+    superseded_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (sop_instance_uid) REFERENCES instances (sop_instance_uid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_superseded_blobs_sop_instance_uid
+    ON superseded_blobs (sop_instance_uid, superseded_at);